@@ -1,3 +1,54 @@
+use std::path::Path;
+use std::process::Command;
+
+#[path = "src/checksum.rs"]
+mod checksum;
+
+use checksum::sha256_file;
+
 fn main() {
+    // Build metadata for get_app_info
+    let git_sha = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    // SOURCE_DATE_EPOCH keeps reproducible builds reproducible
+    let build_date = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|secs| secs.parse::<i64>().ok())
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .unwrap_or_else(chrono::Utc::now)
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    println!("cargo:rustc-env=OVELO_GIT_SHA={}", git_sha);
+    println!("cargo:rustc-env=OVELO_BUILD_DATE={}", build_date);
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    // Checksum of the bundled sidecar, verified before it's launched. Empty when the
+    // binary hasn't been built (dev checkouts), which skips the check.
+    let binary_name = if std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("windows") {
+        "ovelo_server.exe"
+    } else {
+        "ovelo_server"
+    };
+    let sidecar = Path::new("resources").join("backend").join(binary_name);
+    let sidecar_sha = match sha256_file(&sidecar) {
+        Ok(hash) => hash,
+        Err(_) => {
+            if std::env::var("PROFILE").as_deref() == Ok("release") {
+                println!(
+                    "cargo:warning=No bundled sidecar at {}, integrity check disabled",
+                    sidecar.display()
+                );
+            }
+            String::new()
+        }
+    };
+    println!("cargo:rustc-env=OVELO_SIDECAR_SHA256={}", sidecar_sha);
+    println!("cargo:rerun-if-changed={}", sidecar.display());
+
     tauri_build::build()
 }
@@ -0,0 +1,414 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::auto_pause;
+use crate::python_bridge;
+use crate::reflections;
+use crate::settings;
+
+const MAX_RECENT_EVENTS: u32 = 500;
+const EXCLUDED_APPS_ENDPOINT: &str = "/api/excluded_apps";
+const MAX_EXCLUDED_APPS: usize = 200;
+const MAX_APP_NAME_LEN: usize = 256;
+// No sample for this long while tracking isn't paused means the sampler has stopped
+const STALL_THRESHOLD_SECS: u64 = 300;
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusEvent {
+    // Unix seconds, as recorded by the tracker
+    pub timestamp: f64,
+    #[serde(default)]
+    pub app: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub classification: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CurrentActivity {
+    #[serde(default)]
+    pub paused: bool,
+    #[serde(default)]
+    pub app: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    // Unix seconds the current app came into focus
+    #[serde(default)]
+    pub since: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastSample {
+    ts: String,
+    age_secs: u64,
+}
+
+async fn fetch_last_sample() -> Result<LastSample, String> {
+    let res = python_bridge::call_api("/api/last_sample").await?;
+    // RFC 3339 string or Unix seconds, depending on the backend version
+    let ts = res.get("ts").ok_or("Malformed last sample: missing ts")?;
+    let ts = match ts {
+        serde_json::Value::String(ts) => DateTime::parse_from_rfc3339(ts)
+            .map(|ts| ts.with_timezone(&Utc))
+            .map_err(|e| format!("Malformed last sample: {}", e))?,
+        other => other
+            .as_f64()
+            .and_then(|secs| DateTime::from_timestamp(secs as i64, 0))
+            .ok_or("Malformed last sample: bad ts")?,
+    };
+    Ok(LastSample {
+        ts: ts.to_rfc3339(),
+        age_secs: (Utc::now() - ts).num_seconds().max(0) as u64,
+    })
+}
+
+#[tauri::command]
+pub async fn get_last_sample_time() -> Result<LastSample, String> {
+    fetch_last_sample().await
+}
+
+/// Emits `tracking-stalled` once when the newest sample gets too old while tracking is
+/// supposed to be running, which health checks alone can't catch.
+pub fn start_stall_watcher(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut stalled = false;
+        loop {
+            tokio::time::sleep(STALL_CHECK_INTERVAL).await;
+            // Nothing is sampling yet, and polling would start it
+            if python_bridge::is_deferred() {
+                continue;
+            }
+            let (sample, current) =
+                tokio::join!(fetch_last_sample(), python_bridge::call_api("/api/current"));
+            let paused = current
+                .ok()
+                .and_then(|c| c.get("paused").and_then(|p| p.as_bool()))
+                .unwrap_or(false);
+            let Ok(sample) = sample else {
+                continue;
+            };
+
+            let is_stale = !paused && sample.age_secs > STALL_THRESHOLD_SECS;
+            if is_stale && !stalled {
+                eprintln!("Tracking stalled: last sample {} s ago", sample.age_secs);
+                let _ = app_handle.emit("tracking-stalled", &sample);
+            }
+            stalled = is_stale;
+        }
+    });
+}
+
+pub async fn is_tracking_paused() -> Result<bool, String> {
+    let current = python_bridge::call_api("/api/current").await?;
+    Ok(current
+        .get("paused")
+        .and_then(|p| p.as_bool())
+        .unwrap_or(false))
+}
+
+pub async fn set_tracking_paused(paused: bool) -> Result<(), String> {
+    let endpoint = if paused {
+        "/api/pause_tracking"
+    } else {
+        "/api/resume_tracking"
+    };
+    python_bridge::post_api(endpoint, serde_json::json!({})).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn pause_tracking() -> Result<(), String> {
+    set_tracking_paused(true).await
+}
+
+/// Resumes tracking. Inside an auto-pause window this holds until the window's next boundary.
+#[tauri::command]
+pub async fn resume_tracking(app_handle: tauri::AppHandle) -> Result<(), String> {
+    set_tracking_paused(false).await?;
+    auto_pause::on_manual_resume(&app_handle);
+    Ok(())
+}
+
+/// The app in focus right now, for the live "now tracking" indicator. Cheap enough to poll.
+#[tauri::command]
+pub async fn get_current_activity() -> Result<CurrentActivity, String> {
+    let (res, redact) = tokio::join!(
+        python_bridge::call_api("/api/current"),
+        settings::titles_redacted()
+    );
+    let mut current: CurrentActivity =
+        serde_json::from_value(res?).map_err(|e| format!("Malformed current activity: {}", e))?;
+
+    if current.paused {
+        // Don't show the last app seen before the pause as if it were still being tracked
+        return Ok(CurrentActivity {
+            paused: true,
+            ..Default::default()
+        });
+    }
+    if redact {
+        current.title = None;
+    }
+    Ok(current)
+}
+
+#[derive(Deserialize)]
+struct CategoryMinutes {
+    category: String,
+    #[serde(default)]
+    minutes: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryShare {
+    pub category: String,
+    pub minutes: f64,
+    pub percent: f64,
+}
+
+fn round_tenth(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+// Percentages to one decimal, largest first; rounding drift goes to the largest category
+// so the chart always adds up to 100
+fn to_shares(mut categories: Vec<CategoryMinutes>) -> Vec<CategoryShare> {
+    categories.retain(|c| c.minutes > 0.0);
+    let total: f64 = categories.iter().map(|c| c.minutes).sum();
+    if total <= 0.0 {
+        return Vec::new();
+    }
+    categories.sort_by(|a, b| b.minutes.total_cmp(&a.minutes));
+
+    let mut shares: Vec<CategoryShare> = categories
+        .into_iter()
+        .map(|c| CategoryShare {
+            percent: round_tenth(c.minutes / total * 100.0),
+            category: c.category,
+            minutes: c.minutes,
+        })
+        .collect();
+    let assigned: f64 = shares.iter().map(|s| s.percent).sum();
+    if let Some(largest) = shares.first_mut() {
+        largest.percent = round_tenth(largest.percent + 100.0 - assigned);
+    }
+    shares
+}
+
+/// Minutes per app category for one day, for the "where did my time go" chart.
+/// Days with no tracked time give an empty list.
+#[tauri::command]
+pub async fn get_category_breakdown(date: String) -> Result<Vec<CategoryShare>, String> {
+    reflections::parse_date(&date)?;
+    let res = python_bridge::call_api(&format!("/api/categories?date={}", date)).await?;
+
+    let categories = res.get("categories").cloned().unwrap_or(res);
+    if categories.is_null() {
+        return Ok(Vec::new());
+    }
+    let categories: Vec<CategoryMinutes> = serde_json::from_value(categories)
+        .map_err(|e| format!("Malformed categories response: {}", e))?;
+    Ok(to_shares(categories))
+}
+
+#[tauri::command]
+pub async fn get_recent_events(limit: u32) -> Result<Vec<FocusEvent>, String> {
+    let limit = limit.clamp(1, MAX_RECENT_EVENTS);
+    let redact = settings::titles_redacted().await;
+    let res = python_bridge::call_api(&format!(
+        "/api/recent_events?limit={}&redact={}",
+        limit, redact
+    ))
+    .await?;
+
+    let events = res.get("events").cloned().unwrap_or(res);
+    let mut events: Vec<FocusEvent> =
+        serde_json::from_value(events).map_err(|e| format!("Malformed events response: {}", e))?;
+    // Don't rely on the backend alone to honor the privacy setting
+    if redact {
+        for event in &mut events {
+            event.title = None;
+        }
+    }
+    Ok(events)
+}
+
+// Trims, drops blanks and removes case-insensitive duplicates, keeping the first spelling
+fn normalize_apps(apps: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    apps.into_iter()
+        .map(|app| app.trim().to_string())
+        .filter(|app| !app.is_empty() && seen.insert(app.to_lowercase()))
+        .collect()
+}
+
+fn parse_apps(res: serde_json::Value) -> Result<Vec<String>, String> {
+    let apps = res.get("apps").cloned().unwrap_or(res);
+    serde_json::from_value(apps).map_err(|e| format!("Malformed excluded apps response: {}", e))
+}
+
+/// Apps that are never tracked (password managers, banking, ...).
+#[tauri::command]
+pub async fn get_excluded_apps() -> Result<Vec<String>, String> {
+    parse_apps(python_bridge::call_api(EXCLUDED_APPS_ENDPOINT).await?)
+}
+
+#[tauri::command]
+pub async fn set_excluded_apps(apps: Vec<String>) -> Result<Vec<String>, String> {
+    let apps = normalize_apps(apps);
+    if apps.len() > MAX_EXCLUDED_APPS {
+        return Err(format!(
+            "At most {} apps can be excluded",
+            MAX_EXCLUDED_APPS
+        ));
+    }
+    if let Some(app) = apps.iter().find(|app| app.len() > MAX_APP_NAME_LEN) {
+        return Err(format!(
+            "App identifier too long: {}...",
+            app.chars().take(32).collect::<String>()
+        ));
+    }
+
+    let res = python_bridge::post_api(EXCLUDED_APPS_ENDPOINT, serde_json::json!({ "apps": apps }))
+        .await?;
+    // Older backends just acknowledge; fall back to what we sent
+    Ok(parse_apps(res).unwrap_or(apps))
+}
+
+const TITLE_MODE_ENDPOINT: &str = "/api/title_capture_mode";
+// "full" stores titles as seen, "app-only" drops them, "hashed" keeps a one-way hash for grouping
+const TITLE_MODES: &[&str] = &["full", "app-only", "hashed"];
+
+#[derive(Clone, Serialize)]
+struct TitleCaptureModeChanged {
+    mode: String,
+}
+
+/// How the backend stores window titles.
+#[tauri::command]
+pub async fn get_title_capture_mode() -> Result<String, String> {
+    let res = python_bridge::call_api(TITLE_MODE_ENDPOINT).await?;
+    let mode = res
+        .get("mode")
+        .and_then(|m| m.as_str())
+        .ok_or_else(|| "Malformed title capture mode".to_string())?;
+    if !TITLE_MODES.contains(&mode) {
+        return Err(format!(
+            "Backend reported unknown title capture mode: {}",
+            mode
+        ));
+    }
+    Ok(mode.to_string())
+}
+
+/// Changes title storage going forward; titles already stored are left as they are.
+#[tauri::command]
+pub async fn set_title_capture_mode(
+    app_handle: tauri::AppHandle,
+    mode: String,
+) -> Result<String, String> {
+    if !TITLE_MODES.contains(&mode.as_str()) {
+        return Err(format!(
+            "Unknown title capture mode: {} (expected one of {})",
+            mode,
+            TITLE_MODES.join(", ")
+        ));
+    }
+    python_bridge::post_api(TITLE_MODE_ENDPOINT, serde_json::json!({ "mode": mode })).await?;
+    println!("Title capture mode set to {}", mode);
+    let _ = app_handle.emit(
+        "title-capture-mode-changed",
+        TitleCaptureModeChanged { mode: mode.clone() },
+    );
+    Ok(mode)
+}
+
+fn zero_if_null<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    Ok(Option::<u32>::deserialize(deserializer)?.unwrap_or(0))
+}
+
+/// All-time records for the records screen. A new user gets zeros and no best day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Records {
+    #[serde(
+        default,
+        deserialize_with = "zero_if_null",
+        alias = "current_streak_days"
+    )]
+    current_streak_days: u32,
+    #[serde(
+        default,
+        deserialize_with = "zero_if_null",
+        alias = "longest_streak_days"
+    )]
+    longest_streak_days: u32,
+    #[serde(
+        default,
+        deserialize_with = "zero_if_null",
+        alias = "longest_focus_session_minutes"
+    )]
+    longest_focus_session_minutes: u32,
+    // YYYY-MM-DD
+    #[serde(default, alias = "most_focused_day")]
+    most_focused_day: Option<String>,
+}
+
+#[tauri::command]
+pub async fn get_records() -> Result<Records, String> {
+    let res = python_bridge::call_api("/api/records").await?;
+    if res.is_null() {
+        return Ok(Records::default());
+    }
+    serde_json::from_value(res).map_err(|e| format!("Malformed records response: {}", e))
+}
+
+/// Which model or ruleset the backend classifies focus with, for the category legend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassifierInfo {
+    #[serde(default = "unknown")]
+    kind: String,
+    #[serde(default = "unknown")]
+    version: String,
+    #[serde(default)]
+    categories: Vec<String>,
+}
+
+fn unknown() -> String {
+    "unknown".to_string()
+}
+
+impl Default for ClassifierInfo {
+    fn default() -> Self {
+        Self {
+            kind: unknown(),
+            version: unknown(),
+            categories: Vec::new(),
+        }
+    }
+}
+
+/// Classifier details, or the "unknown" default from backends that predate the endpoint.
+pub async fn fetch_classifier_info() -> Result<ClassifierInfo, String> {
+    match python_bridge::call_api("/api/classifier").await {
+        Ok(res) if res.is_null() => Ok(ClassifierInfo::default()),
+        Ok(res) => {
+            serde_json::from_value(res).map_err(|e| format!("Malformed classifier info: {}", e))
+        }
+        Err(e) if e.starts_with("API Error: 404") => Ok(ClassifierInfo::default()),
+        Err(e) => Err(e),
+    }
+}
+
+#[tauri::command]
+pub async fn get_classifier_info() -> Result<ClassifierInfo, String> {
+    fetch_classifier_info().await
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::cache;
+use crate::goals::{local_midnight, MAX_SAMPLE_GAP_SECS};
+use crate::local_store::LocalStore;
+use crate::notifications;
+use crate::python_bridge;
+use crate::today_stream;
+
+const LIMITS_ENDPOINT: &str = "/api/app_limits";
+// Limits change rarely; the watcher runs on every today_state update
+const LIMITS_TTL: Duration = Duration::from_secs(300);
+// Apps already warned about today, so each limit notifies once per local day
+const NOTIFIED_KEY: &str = "app_limits_notified";
+const MAX_LIMIT_MINUTES: u32 = 24 * 60;
+const MAX_APP_NAME_LEN: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppLimit {
+    pub app: String,
+    pub minutes: u32,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct NotifiedToday {
+    date: Option<NaiveDate>,
+    apps: Vec<String>,
+}
+
+// Limits match the tracker's app names case-insensitively
+fn normalize_app(app: &str) -> Result<String, String> {
+    let app = app.trim().to_lowercase();
+    if app.is_empty() {
+        return Err("App identifier is empty".to_string());
+    }
+    if app.len() > MAX_APP_NAME_LEN {
+        return Err(format!(
+            "App identifier too long: {}...",
+            app.chars().take(32).collect::<String>()
+        ));
+    }
+    Ok(app)
+}
+
+async fn fetch_limits() -> Result<Vec<AppLimit>, String> {
+    let res = cache::call_api_cached(LIMITS_ENDPOINT, LIMITS_TTL).await?;
+    let limits = res.get("limits").cloned().unwrap_or(res);
+    serde_json::from_value(limits).map_err(|e| format!("Malformed app limits: {}", e))
+}
+
+// Minutes per app (lowercased) since local midnight, from each sample's dominant app
+fn app_minutes_today(state: &serde_json::Value) -> HashMap<String, f64> {
+    let mut minutes = HashMap::new();
+    let Some(timeline) = state.get("timeline").and_then(|t| t.as_array()) else {
+        return minutes;
+    };
+    let midnight = local_midnight();
+
+    let points: Vec<(f64, Option<String>)> = timeline
+        .iter()
+        .filter_map(|point| {
+            let timestamp = point.get("timestamp")?.as_f64()?;
+            let app = point
+                .get("dominant_app")
+                .and_then(|a| a.as_str())
+                .map(|a| a.trim().to_lowercase());
+            Some((timestamp, app))
+        })
+        .filter(|(timestamp, _)| *timestamp >= midnight)
+        .collect();
+
+    for pair in points.windows(2) {
+        if let Some(app) = &pair[0].1 {
+            let seconds = (pair[1].0 - pair[0].0).clamp(0.0, MAX_SAMPLE_GAP_SECS);
+            *minutes.entry(app.clone()).or_insert(0.0) += seconds / 60.0;
+        }
+    }
+    minutes
+}
+
+async fn check_limits(app_handle: &tauri::AppHandle, state: &serde_json::Value) {
+    let limits = match fetch_limits().await {
+        Ok(limits) if !limits.is_empty() => limits,
+        Ok(_) => return,
+        Err(e) => {
+            eprintln!("App limit check skipped: {}", e);
+            return;
+        }
+    };
+    let usage = app_minutes_today(state);
+
+    let store = app_handle.state::<LocalStore>();
+    let today = Local::now().date_naive();
+    let mut notified = store
+        .get::<NotifiedToday>(NOTIFIED_KEY)
+        .filter(|n| n.date == Some(today))
+        .unwrap_or_default();
+
+    let mut crossed = false;
+    for limit in &limits {
+        let app = limit.app.trim().to_lowercase();
+        let used = usage.get(&app).copied().unwrap_or(0.0);
+        if used < limit.minutes as f64 || notified.apps.contains(&app) {
+            continue;
+        }
+        notified.apps.push(app);
+        crossed = true;
+        notifications::notify(
+            app_handle,
+            "Ovelo",
+            &format!(
+                "{} has reached its daily limit of {} minutes",
+                limit.app, limit.minutes
+            ),
+        );
+    }
+    if crossed {
+        notified.date = Some(today);
+        if let Err(e) = store.set(NOTIFIED_KEY, &notified) {
+            eprintln!("Failed to record app limit notifications: {}", e);
+        }
+    }
+}
+
+/// Watches today_state updates for an app going over its daily limit.
+pub fn start_watcher(app_handle: tauri::AppHandle) {
+    let mut updates = today_stream::subscribe();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match updates.recv().await {
+                Ok(state) => check_limits(&app_handle, &state).await,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_app_limits() -> Result<Vec<AppLimit>, String> {
+    fetch_limits().await
+}
+
+/// Sets (or replaces) the daily limit for one app.
+#[tauri::command]
+pub async fn set_app_limit(limit: AppLimit) -> Result<AppLimit, String> {
+    let app = normalize_app(&limit.app)?;
+    if limit.minutes == 0 || limit.minutes > MAX_LIMIT_MINUTES {
+        return Err(format!(
+            "Limit must be between 1 and {} minutes",
+            MAX_LIMIT_MINUTES
+        ));
+    }
+    let limit = AppLimit {
+        app,
+        minutes: limit.minutes,
+    };
+    let body = serde_json::to_value(&limit).map_err(|e| e.to_string())?;
+    python_bridge::post_api(LIMITS_ENDPOINT, body).await?;
+    cache::invalidate(LIMITS_ENDPOINT);
+    Ok(limit)
+}
+
+#[tauri::command]
+pub async fn remove_app_limit(app: String) -> Result<(), String> {
+    let app = normalize_app(&app)?;
+    python_bridge::post_api("/api/remove_app_limit", serde_json::json!({ "app": app })).await?;
+    cache::invalidate(LIMITS_ENDPOINT);
+    Ok(())
+}
@@ -0,0 +1,409 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeZone, Weekday};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use crate::activity;
+use crate::local_store::LocalStore;
+use crate::python_bridge;
+
+const CONFIG_KEY: &str = "auto_pause_schedule";
+// Persisted so a pause we started is still lifted if the app was closed during the window
+const ACTIVE_KEY: &str = "auto_pause_active_until";
+// Persisted so a manual resume still holds if the app is relaunched during the window
+const OVERRIDE_KEY: &str = "auto_pause_override_until";
+const MAX_WINDOWS: usize = 32;
+// Sleeps are capped so DST shifts, clock changes and system sleep are caught by re-reading
+// the local clock
+const MAX_SLEEP: Duration = Duration::from_secs(15 * 60);
+// Retry delay when the backend didn't take a pause or resume
+const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+// Bumped on every reschedule; a timer whose generation is stale exits
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Recurring window during which tracking is paused, e.g. a standing meeting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PauseWindow {
+    /// "mon" ... "sun"
+    pub days: Vec<String>,
+    /// Local "HH:MM". An end at or before the start runs past midnight into the next day.
+    pub start: String,
+    pub end: String,
+}
+
+// A window in progress that we've acted on
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ActivePause {
+    // End of the (merged) window
+    until: DateTime<Local>,
+    // Tracking was running when the window began, so it's resumed when the window ends.
+    // False when the user had already paused by hand.
+    resume: bool,
+}
+
+struct AutoPauseState {
+    active: Option<ActivePause>,
+    // The user resumed during a window; don't pause again before this
+    override_until: Option<DateTime<Local>>,
+}
+
+static STATE: Mutex<AutoPauseState> = Mutex::new(AutoPauseState {
+    active: None,
+    override_until: None,
+});
+
+#[derive(Clone, Serialize)]
+struct AutoPauseActivated {
+    until: String,
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time (expected HH:MM): {}", time))
+}
+
+fn parse_day(day: &str) -> Result<Weekday, String> {
+    Weekday::from_str(day.trim()).map_err(|_| format!("Invalid day: {}", day))
+}
+
+// Local instant for a wall-clock time. A time skipped by a DST jump resolves to the hour
+// after it; a repeated one to its first occurrence.
+fn local_at<Tz: TimeZone>(tz: &Tz, date: NaiveDate, time: NaiveTime) -> Option<DateTime<Tz>> {
+    let naive = date.and_time(time);
+    tz.from_local_datetime(&naive).earliest().or_else(|| {
+        tz.from_local_datetime(&(naive + chrono::Duration::hours(1)))
+            .earliest()
+    })
+}
+
+// Concrete windows from yesterday (for ones running past midnight) to a week out, sorted,
+// with overlapping or touching windows merged into one
+fn spans<Tz: TimeZone>(
+    windows: &[PauseWindow],
+    now: &DateTime<Tz>,
+) -> Vec<(DateTime<Tz>, DateTime<Tz>)> {
+    let tz = now.timezone();
+    let yesterday = now.date_naive() - chrono::Days::new(1);
+    let mut spans = Vec::new();
+    for window in windows {
+        let (Ok(start), Ok(end)) = (parse_time(&window.start), parse_time(&window.end)) else {
+            continue;
+        };
+        let days: Vec<Weekday> = window
+            .days
+            .iter()
+            .filter_map(|d| parse_day(d).ok())
+            .collect();
+        for date in yesterday.iter_days().take(9) {
+            if !days.contains(&date.weekday()) {
+                continue;
+            }
+            let end_date = if end <= start {
+                date.succ_opt()
+            } else {
+                Some(date)
+            };
+            let span = local_at(&tz, date, start).zip(end_date.and_then(|d| local_at(&tz, d, end)));
+            if let Some((from, to)) = span.filter(|(from, to)| to > from) {
+                spans.push((from, to));
+            }
+        }
+    }
+    spans.sort();
+
+    let mut merged: Vec<(DateTime<Tz>, DateTime<Tz>)> = Vec::new();
+    for (from, to) in spans {
+        match merged.last_mut() {
+            Some(last) if from <= last.1 => {
+                if to > last.1 {
+                    last.1 = to;
+                }
+            }
+            _ => merged.push((from, to)),
+        }
+    }
+    merged
+}
+
+fn load(app_handle: &tauri::AppHandle) -> Vec<PauseWindow> {
+    app_handle
+        .state::<LocalStore>()
+        .get(CONFIG_KEY)
+        .unwrap_or_default()
+}
+
+fn persist<T: Serialize>(app_handle: &tauri::AppHandle, key: &str, value: Option<T>) {
+    let store = app_handle.state::<LocalStore>();
+    let result = match value {
+        Some(value) => store.set(key, value),
+        None => store.remove(key),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to record auto-pause state: {}", e);
+    }
+}
+
+enum Action {
+    Pause(DateTime<Local>),
+    End(ActivePause),
+}
+
+// Pauses or resumes as the current time requires and returns when to look again
+async fn tick(app_handle: &tauri::AppHandle, windows: &[PauseWindow]) -> Option<DateTime<Local>> {
+    let now = Local::now();
+    let spans = spans(windows, &now);
+    let current_end = spans
+        .iter()
+        .find(|(from, to)| *from <= now && now < *to)
+        .map(|(_, to)| *to);
+    let next_start = spans.iter().map(|(from, _)| *from).find(|from| *from > now);
+
+    let action = {
+        let mut state = STATE.lock().unwrap();
+        if state.override_until.is_some_and(|until| now >= until) {
+            state.override_until = None;
+            persist::<DateTime<Local>>(app_handle, OVERRIDE_KEY, None);
+        }
+        match current_end {
+            Some(end) if state.active.is_none() && state.override_until.is_none() => {
+                Some(Action::Pause(end))
+            }
+            Some(end) => {
+                // A schedule change may have moved the end of the running window
+                if let Some(active) = state.active.as_mut() {
+                    active.until = end;
+                }
+                None
+            }
+            None => state.active.map(Action::End),
+        }
+    };
+
+    let retry = Some(now + RETRY_DELAY);
+    match action {
+        Some(Action::Pause(end)) => {
+            let was_paused = match activity::is_tracking_paused().await {
+                Ok(paused) => paused,
+                Err(e) => {
+                    eprintln!("Auto-pause failed: {}", e);
+                    return retry;
+                }
+            };
+            if !was_paused {
+                if let Err(e) = activity::set_tracking_paused(true).await {
+                    eprintln!("Auto-pause failed: {}", e);
+                    return retry;
+                }
+            }
+            let active = ActivePause {
+                until: end,
+                resume: !was_paused,
+            };
+            STATE.lock().unwrap().active = Some(active);
+            persist(app_handle, ACTIVE_KEY, Some(active));
+            if was_paused {
+                println!(
+                    "Tracking already paused, leaving it to the user until {}",
+                    end
+                );
+            } else {
+                println!("Tracking auto-paused until {}", end);
+                let _ = app_handle.emit(
+                    "auto-pause-activated",
+                    AutoPauseActivated {
+                        until: end.to_rfc3339(),
+                    },
+                );
+            }
+        }
+        Some(Action::End(active)) => {
+            if active.resume {
+                if let Err(e) = activity::set_tracking_paused(false).await {
+                    eprintln!("Auto-pause resume failed: {}", e);
+                    return retry;
+                }
+            }
+            STATE.lock().unwrap().active = None;
+            persist::<ActivePause>(app_handle, ACTIVE_KEY, None);
+            if active.resume {
+                println!("Auto-pause ended");
+                let _ = app_handle.emit("auto-pause-ended", ());
+            }
+        }
+        None => {}
+    }
+    current_end.or(next_start)
+}
+
+/// Called when the user resumes tracking by hand: an auto-pause in progress stays lifted
+/// until the end of its window.
+pub fn on_manual_resume(app_handle: &tauri::AppHandle) {
+    let mut state = STATE.lock().unwrap();
+    if let Some(active) = state.active.take() {
+        state.override_until = Some(active.until);
+        persist::<ActivePause>(app_handle, ACTIVE_KEY, None);
+        persist(app_handle, OVERRIDE_KEY, Some(active.until));
+    }
+}
+
+/// (Re)starts the auto-pause timer from the stored schedule, replacing any running one.
+pub fn schedule(app_handle: tauri::AppHandle) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let windows = load(&app_handle);
+    {
+        let store = app_handle.state::<LocalStore>();
+        let mut state = STATE.lock().unwrap();
+        if state.active.is_none() {
+            state.active = store.get(ACTIVE_KEY);
+        }
+        if state.override_until.is_none() {
+            state.override_until = store.get(OVERRIDE_KEY);
+        }
+    }
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            // Nothing is tracked before a lazy start, so there's nothing to pause yet
+            python_bridge::wait_until_started().await;
+            if GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let boundary = tick(&app_handle, &windows).await;
+            // An empty schedule only needs the one tick, to lift a pause it had started
+            if windows.is_empty() && STATE.lock().unwrap().active.is_none() {
+                return;
+            }
+            let wait = boundary
+                .and_then(|at| (at - Local::now()).to_std().ok())
+                .unwrap_or(MAX_SLEEP)
+                .min(MAX_SLEEP);
+            tokio::time::sleep(wait).await;
+            if GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_auto_pause_schedule(app_handle: tauri::AppHandle) -> Vec<PauseWindow> {
+    load(&app_handle)
+}
+
+#[tauri::command]
+pub fn set_auto_pause_schedule(
+    app_handle: tauri::AppHandle,
+    windows: Vec<PauseWindow>,
+) -> Result<Vec<PauseWindow>, String> {
+    if windows.len() > MAX_WINDOWS {
+        return Err(format!("At most {} pause windows are allowed", MAX_WINDOWS));
+    }
+    let mut normalized = Vec::with_capacity(windows.len());
+    for window in windows {
+        if window.days.is_empty() {
+            return Err("Each pause window needs at least one day".to_string());
+        }
+        let mut days = Vec::new();
+        for day in &window.days {
+            let day = parse_day(day)?.to_string().to_lowercase();
+            if !days.contains(&day) {
+                days.push(day);
+            }
+        }
+        let (start, end) = (parse_time(&window.start)?, parse_time(&window.end)?);
+        normalized.push(PauseWindow {
+            days,
+            start: start.format("%H:%M").to_string(),
+            end: end.format("%H:%M").to_string(),
+        });
+    }
+
+    app_handle
+        .state::<LocalStore>()
+        .set(CONFIG_KEY, &normalized)?;
+    schedule(app_handle);
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use chrono_tz::Europe::Berlin;
+
+    fn window(days: &[&str], start: &str, end: &str) -> PauseWindow {
+        PauseWindow {
+            days: days.iter().map(|d| d.to_string()).collect(),
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn spans_merge_overlapping_and_touching_windows() {
+        let windows = [
+            window(&["mon"], "09:00", "10:00"),
+            window(&["mon"], "09:30", "11:00"),
+            window(&["mon"], "11:00", "11:30"),
+        ];
+        // Monday
+        let now = Utc.with_ymd_and_hms(2024, 5, 6, 8, 0, 0).unwrap();
+        let spans = spans(&windows, &now);
+        assert_eq!(
+            spans[0],
+            (
+                Utc.with_ymd_and_hms(2024, 5, 6, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 5, 6, 11, 30, 0).unwrap()
+            )
+        );
+        // The following Monday is the only other one in range
+        assert_eq!(spans.len(), 2);
+    }
+
+    #[test]
+    fn spans_cross_midnight() {
+        let windows = [window(&["fri"], "22:00", "02:00")];
+        // Saturday, inside Friday's window
+        let now = Utc.with_ymd_and_hms(2024, 5, 11, 1, 0, 0).unwrap();
+        let spans = spans(&windows, &now);
+        assert_eq!(
+            spans[0],
+            (
+                Utc.with_ymd_and_hms(2024, 5, 10, 22, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 5, 11, 2, 0, 0).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn spans_resolve_dst_gap_and_overlap() {
+        let windows = [window(&["sun"], "02:30", "05:00")];
+
+        // 02:30 doesn't exist on 31 March in Berlin; the window starts an hour later
+        let now = Berlin.with_ymd_and_hms(2024, 3, 31, 0, 0, 0).unwrap();
+        let (from, to) = spans(&windows, &now)[0];
+        assert_eq!(
+            from,
+            Berlin.with_ymd_and_hms(2024, 3, 31, 3, 30, 0).unwrap()
+        );
+        assert_eq!(
+            from.with_timezone(&Utc).to_rfc3339(),
+            "2024-03-31T01:30:00+00:00"
+        );
+        assert_eq!(to, Berlin.with_ymd_and_hms(2024, 3, 31, 5, 0, 0).unwrap());
+
+        // 02:30 happens twice on 27 October; the first (summer time) one is used
+        let now = Berlin.with_ymd_and_hms(2024, 10, 27, 0, 0, 0).unwrap();
+        let (from, _) = spans(&windows, &now)[0];
+        assert_eq!(
+            from.with_timezone(&Utc).to_rfc3339(),
+            "2024-10-27T00:30:00+00:00"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tauri::{Manager, State};
+use zip::write::SimpleFileOptions;
+
+use crate::cache;
+use crate::local_store::LocalStore;
+use crate::paths;
+use crate::python_bridge::{self, PythonSidecar};
+
+const BACKUP_DIR: &str = "backups";
+const BACKUP_PREFIX: &str = "ovelo-backup-";
+const KEEP_BACKUPS: usize = 7;
+
+const INTERVAL_KEY: &str = "backup_interval_hours";
+const DEFAULT_INTERVAL_HOURS: u64 = 24;
+const RESTORE_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Serializes backup, prune and restore so they never interleave
+static BACKUP_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+// Backup currently being restored; pruning leaves it alone
+static RESTORING: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    path: PathBuf,
+    size_bytes: u64,
+}
+
+fn backup_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = paths::backend_data_dir(app_handle)?.join(BACKUP_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+// Backups sorted oldest first (timestamped names sort chronologically)
+fn existing_backups(dir: &Path) -> Vec<PathBuf> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| {
+                            name.starts_with(BACKUP_PREFIX) && name.ends_with(".zip")
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    backups.sort();
+    backups
+}
+
+// Only top-level data files are backed up; logs and nested folders (backups included) are skipped
+fn write_backup(data_dir: &Path, dest: &Path) -> Result<u64, String> {
+    let file = File::create(dest).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    for entry in std::fs::read_dir(data_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !path.is_file() || name.ends_with(".log") || name.ends_with(".tmp") {
+            continue;
+        }
+        let mut contents = Vec::new();
+        File::open(&path)
+            .and_then(|mut f| f.read_to_end(&mut contents))
+            .map_err(|e| e.to_string())?;
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(&contents).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+
+    std::fs::metadata(dest)
+        .map(|m| m.len())
+        .map_err(|e| e.to_string())
+}
+
+fn prune(dir: &Path) {
+    let backups = existing_backups(dir);
+    let restoring = RESTORING.lock().unwrap().clone();
+    let excess = backups.len().saturating_sub(KEEP_BACKUPS);
+    for old in backups.into_iter().take(excess) {
+        if restoring.as_ref() == Some(&old) {
+            continue;
+        }
+        if let Err(e) = std::fs::remove_file(&old) {
+            eprintln!("Failed to prune backup {:?}: {}", old, e);
+        }
+    }
+}
+
+async fn run_backup(app_handle: &tauri::AppHandle) -> Result<BackupInfo, String> {
+    let _guard = BACKUP_LOCK.lock().await;
+    let data_dir = paths::backend_data_dir(app_handle)?;
+    let dir = backup_dir(app_handle)?;
+    let name = format!(
+        "{}{}.zip",
+        BACKUP_PREFIX,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    let dest = dir.join(name);
+
+    let info = tokio::task::spawn_blocking(move || {
+        let result = write_backup(&data_dir, &dest);
+        if result.is_err() {
+            let _ = std::fs::remove_file(&dest);
+        } else {
+            prune(&dir);
+        }
+        result.map(|size_bytes| BackupInfo {
+            path: dest,
+            size_bytes,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    println!("Created backup {:?} ({} bytes)", info.path, info.size_bytes);
+    Ok(info)
+}
+
+fn interval(app_handle: &tauri::AppHandle) -> Duration {
+    let hours = app_handle
+        .state::<LocalStore>()
+        .get::<u64>(INTERVAL_KEY)
+        .unwrap_or(DEFAULT_INTERVAL_HOURS)
+        .max(1);
+    Duration::from_secs(hours * 3600)
+}
+
+/// Runs backups on the configured interval for as long as the app is open,
+/// counting from the newest existing backup so restarts don't reset the clock.
+pub fn start_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let interval = interval(&app_handle);
+            let since_last = backup_dir(&app_handle)
+                .ok()
+                .and_then(|dir| existing_backups(&dir).pop())
+                .and_then(|newest| std::fs::metadata(newest).and_then(|m| m.modified()).ok())
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .unwrap_or(interval);
+            tokio::time::sleep(interval.saturating_sub(since_last)).await;
+
+            if let Err(e) = run_backup(&app_handle).await {
+                eprintln!("Scheduled backup failed: {}", e);
+                // Don't spin on a persistent failure
+                tokio::time::sleep(interval).await;
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn create_backup(app_handle: tauri::AppHandle) -> Result<BackupInfo, String> {
+    run_backup(&app_handle).await
+}
+
+#[tauri::command]
+pub async fn list_backups(app_handle: tauri::AppHandle) -> Result<Vec<BackupInfo>, String> {
+    let dir = backup_dir(&app_handle)?;
+    Ok(existing_backups(&dir)
+        .into_iter()
+        .rev()
+        .map(|path| BackupInfo {
+            size_bytes: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            path,
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn set_backup_interval(app_handle: tauri::AppHandle, hours: u64) -> Result<u64, String> {
+    if hours == 0 {
+        return Err("Backup interval must be at least one hour".to_string());
+    }
+    app_handle.state::<LocalStore>().set(INTERVAL_KEY, hours)?;
+    Ok(hours)
+}
+
+// Unpacked here first, so a bad entry or a full disk leaves the live files untouched
+const RESTORE_STAGING_DIR: &str = "restore-staging";
+
+fn extract_entries(archive: &Path, staging: &Path) -> Result<Vec<PathBuf>, String> {
+    let file = File::open(archive).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut names = Vec::new();
+    for i in 0..zip.len() {
+        let mut entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        // enclosed_name rejects absolute paths and `..` traversal
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        let mut out = File::create(staging.join(&name)).map_err(|e| e.to_string())?;
+        std::io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        out.sync_all().map_err(|e| e.to_string())?;
+        names.push(name);
+    }
+    Ok(names)
+}
+
+// Extracts every entry into a staging dir, then renames them over the live files
+fn extract_backup(archive: &Path, data_dir: &Path) -> Result<(), String> {
+    let staging = data_dir.join(RESTORE_STAGING_DIR);
+    let _ = std::fs::remove_dir_all(&staging);
+    std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+
+    let result = extract_entries(archive, &staging).and_then(|names| {
+        names.iter().try_for_each(|name| {
+            std::fs::rename(staging.join(name), data_dir.join(name))
+                .map_err(|e| format!("Failed to restore {}: {}", name.display(), e))
+        })
+    });
+    if let Err(e) = std::fs::remove_dir_all(&staging) {
+        eprintln!("Failed to remove restore staging dir: {}", e);
+    }
+    result
+}
+
+/// Restores a backup over the live data. The sidecar is stopped for the duration
+/// so it can't write over the restored files, then started again.
+#[tauri::command]
+pub async fn restore_backup(
+    app_handle: tauri::AppHandle,
+    sidecar: State<'_, Arc<PythonSidecar>>,
+    path: String,
+) -> Result<(), String> {
+    python_bridge::ensure_writable()?;
+    let _guard = BACKUP_LOCK.lock().await;
+    let dir = backup_dir(&app_handle)?;
+    let archive = PathBuf::from(path);
+    if !existing_backups(&dir).contains(&archive) {
+        return Err(format!("Not a known backup: {}", archive.display()));
+    }
+    let data_dir = paths::backend_data_dir(&app_handle)?;
+
+    // Let pending saves land rather than have them written over by the restore
+    python_bridge::drain_mutations(RESTORE_DRAIN_TIMEOUT).await?;
+    *RESTORING.lock().unwrap() = Some(archive.clone());
+    sidecar.stop();
+    let extract_archive = archive.clone();
+    let result = tokio::task::spawn_blocking(move || extract_backup(&extract_archive, &data_dir))
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r);
+    *RESTORING.lock().unwrap() = None;
+
+    // Cached settings, limits and personas describe the data that was just replaced
+    cache::clear();
+    sidecar.start_async(&app_handle).await;
+    crate::spawn_ready_watch(app_handle.clone(), sidecar.inner().clone());
+    result.map(|()| println!("Restored backup {:?}", archive))
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::python_bridge;
+
+// Short-lived cache of backend GET responses, keyed by endpoint
+static CACHE: Mutex<Option<HashMap<String, (Instant, serde_json::Value)>>> = Mutex::new(None);
+
+pub fn get(key: &str, ttl: Duration) -> Option<serde_json::Value> {
+    let cache = CACHE.lock().unwrap();
+    cache
+        .as_ref()?
+        .get(key)
+        .filter(|(stored_at, _)| stored_at.elapsed() < ttl)
+        .map(|(_, value)| value.clone())
+}
+
+pub fn put(key: &str, value: serde_json::Value) {
+    let mut cache = CACHE.lock().unwrap();
+    cache
+        .get_or_insert_with(HashMap::new)
+        .insert(key.to_string(), (Instant::now(), value));
+}
+
+pub fn invalidate(key: &str) {
+    if let Some(cache) = CACHE.lock().unwrap().as_mut() {
+        cache.remove(key);
+    }
+}
+
+pub fn clear() {
+    CACHE.lock().unwrap().take();
+}
+
+/// GETs `endpoint`, serving a cached copy if it is younger than `ttl`.
+pub async fn call_api_cached(endpoint: &str, ttl: Duration) -> Result<serde_json::Value, String> {
+    if let Some(value) = get(endpoint, ttl) {
+        return Ok(value);
+    }
+    let value = python_bridge::call_api(endpoint).await?;
+    put(endpoint, value.clone());
+    Ok(value)
+}
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::power;
+use crate::python_bridge::{self, PythonSidecar};
+
+const TICK: Duration = Duration::from_secs(5);
+// Real time beyond the tick that counts as the machine having been asleep
+const SLEEP_GAP: Duration = Duration::from_secs(30);
+// Wall-clock drift from real time that counts as the clock being changed; NTP slews
+// and small step corrections stay well below this
+const JUMP_THRESHOLD_SECS: f64 = 30.0;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ClockChanged {
+    delta_seconds: i64,
+}
+
+// Sleep is measured as the difference between two clocks: one that keeps counting while the
+// machine is suspended and one that doesn't. A scheduler stall or a wall-clock change moves
+// both (or neither) the same, so neither is mistaken for a sleep.
+
+/// Time including suspend, where the OS exposes it.
+#[cfg(target_os = "linux")]
+fn boot_clock() -> Option<Duration> {
+    let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+    let seconds: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    Some(Duration::from_secs_f64(seconds))
+}
+
+// `Instant` keeps counting through sleep on Windows, so it already is the boot clock
+#[cfg(target_os = "windows")]
+fn boot_clock() -> Option<Duration> {
+    Some(instant_elapsed())
+}
+
+// Darwin's CLOCK_MONOTONIC, unlike the clock behind `Instant`, counts through sleep
+#[cfg(target_os = "macos")]
+fn boot_clock() -> Option<Duration> {
+    let mut ts = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    if unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) } != 0 {
+        return None;
+    }
+    Some(Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn boot_clock() -> Option<Duration> {
+    None
+}
+
+/// Time the machine has been awake, excluding suspend.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn awake_clock() -> Option<Duration> {
+    Some(instant_elapsed())
+}
+
+#[cfg(target_os = "windows")]
+fn awake_clock() -> Option<Duration> {
+    use windows::Win32::System::WindowsProgramming::QueryUnbiasedInterruptTime;
+
+    // 100 ns units, not advanced while asleep or hibernating
+    let mut ticks = 0u64;
+    unsafe { QueryUnbiasedInterruptTime(&mut ticks) }
+        .ok()
+        .ok()?;
+    Some(Duration::from_nanos(ticks.saturating_mul(100)))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn awake_clock() -> Option<Duration> {
+    None
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows", target_os = "macos"))]
+fn instant_elapsed() -> Duration {
+    static ORIGIN: std::sync::OnceLock<Instant> = std::sync::OnceLock::new();
+    ORIGIN.get_or_init(Instant::now).elapsed()
+}
+
+fn step(before: Option<Duration>, after: Option<Duration>) -> Option<f64> {
+    Some(after?.saturating_sub(before?).as_secs_f64())
+}
+
+// Signed wall-clock change between two readings, in seconds
+fn wall_delta(from: SystemTime, to: SystemTime) -> f64 {
+    match to.duration_since(from) {
+        Ok(forward) => forward.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
+
+async fn on_clock_jump(
+    app_handle: &tauri::AppHandle,
+    from: SystemTime,
+    to: SystemTime,
+    drift: f64,
+) {
+    let delta_seconds = drift.round() as i64;
+    println!("System clock changed by {} s", delta_seconds);
+    let body = serde_json::json!({
+        "from": chrono::DateTime::<chrono::Utc>::from(from).to_rfc3339(),
+        "to": chrono::DateTime::<chrono::Utc>::from(to).to_rfc3339(),
+        "deltaSeconds": delta_seconds,
+    });
+    // A sidecar that hasn't started yet has nothing recorded to correct
+    if !python_bridge::is_deferred() {
+        if let Err(e) = python_bridge::post_api("/api/clock_jumped", body).await {
+            eprintln!("Failed to notify backend of clock change: {}", e);
+        }
+    }
+    let _ = app_handle.emit("clock-changed", ClockChanged { delta_seconds });
+}
+
+/// Watches for sleeps and clock changes by comparing clocks every tick. These aren't OS power
+/// notifications: a sleep is only seen on the first tick after waking (up to TICK late), and
+/// one shorter than SLEEP_GAP isn't reported. Time spent suspended is the suspend-counting
+/// clock's step minus the awake clock's; a clock change is the wall clock moving differently
+/// from the suspend-counting one. On platforms without both clocks a tick arriving long after
+/// it was due counts as a sleep, so there a long stall or a forward clock change looks like one.
+pub fn start_watcher(app_handle: tauri::AppHandle, sidecar: Arc<PythonSidecar>) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_wall = SystemTime::now();
+        let mut last_mono = Instant::now();
+        let (mut last_boot, mut last_awake) = (boot_clock(), awake_clock());
+        loop {
+            tokio::time::sleep(TICK).await;
+            let (wall, mono) = (SystemTime::now(), Instant::now());
+            let (boot, awake) = (boot_clock(), awake_clock());
+            let wall_step = wall_delta(last_wall, wall);
+            let boot_step = step(last_boot, boot);
+
+            let measured = boot_step.zip(step(last_awake, awake));
+            let slept = match measured {
+                Some((boot_step, awake_step)) => boot_step - awake_step > SLEEP_GAP.as_secs_f64(),
+                None => wall_step > (TICK + SLEEP_GAP).as_secs_f64(),
+            };
+            // Real time passed, counting any sleep if the OS lets us
+            let real_step = boot_step.unwrap_or_else(|| (mono - last_mono).as_secs_f64());
+            let drift = wall_step - real_step;
+
+            if slept {
+                power::on_wake(&app_handle, &sidecar, last_wall, wall).await;
+            }
+            if (boot_step.is_some() || !slept) && drift.abs() > JUMP_THRESHOLD_SECS {
+                on_clock_jump(&app_handle, last_wall, wall, drift).await;
+            }
+            last_wall = wall;
+            last_mono = mono;
+            last_boot = boot;
+            last_awake = awake;
+        }
+    });
+}
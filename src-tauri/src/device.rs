@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use tokio::sync::Mutex;
+
+use crate::paths;
+use crate::python_bridge;
+
+const DEVICE_ID_FILE: &str = "device_id.txt";
+
+// Serializes regeneration so two rapid calls can't write different ids
+static REGENERATE_LOCK: Mutex<()> = Mutex::const_new(());
+
+fn id_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::app_data_dir(app_handle)?.join(DEVICE_ID_FILE))
+}
+
+// The shell's copy is the source of truth; the backend's is kept in step with it
+fn read_local(app_handle: &tauri::AppHandle) -> Option<String> {
+    let raw = std::fs::read_to_string(id_path(app_handle).ok()?).ok()?;
+    let id = raw.trim();
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+fn write_local(app_handle: &tauri::AppHandle, device_id: &str) -> Result<(), String> {
+    std::fs::write(id_path(app_handle)?, device_id).map_err(|e| e.to_string())
+}
+
+async fn push(device_id: &str) -> Result<serde_json::Value, String> {
+    let body = serde_json::json!({ "deviceId": device_id });
+    python_bridge::post_api("/api/sync_device_id", body).await
+}
+
+/// Pushes the local device id to the backend. Run whenever the sidecar becomes ready, so an
+/// id whose sync failed earlier is retried.
+pub async fn sync_on_ready(app_handle: &tauri::AppHandle) {
+    let Some(device_id) = read_local(app_handle) else {
+        return;
+    };
+    if let Err(e) = push(&device_id).await {
+        eprintln!("Device ID sync failed: {}", e);
+    }
+}
+
+/// Returns the device id, from the app data dir when there is one so it's available before
+/// the sidecar is. Otherwise the backend's id is fetched and saved locally.
+#[tauri::command]
+pub async fn get_device_id(app_handle: tauri::AppHandle) -> Result<serde_json::Value, String> {
+    if let Some(device_id) = read_local(&app_handle) {
+        return Ok(serde_json::json!({ "success": true, "deviceId": device_id }));
+    }
+    let res = python_bridge::call_api("/api/get_device_id").await?;
+    if let Some(device_id) = res.get("deviceId").and_then(|id| id.as_str()) {
+        if let Err(e) = write_local(&app_handle, device_id) {
+            eprintln!("Failed to save device ID locally: {}", e);
+        }
+    }
+    Ok(res)
+}
+
+/// Adopts `device_id` (e.g. the frontend's fallback id) locally and on the backend.
+#[tauri::command]
+pub async fn sync_device_id(
+    app_handle: tauri::AppHandle,
+    device_id: String,
+) -> Result<serde_json::Value, String> {
+    let device_id = device_id.trim();
+    if device_id.is_empty() {
+        return Err("Device ID is empty".to_string());
+    }
+    write_local(&app_handle, device_id)?;
+    push(device_id).await
+}
+
+/// Generates a fresh device id, stores it in the app data dir and pushes it to the backend.
+/// The id is written locally first so it survives even if the sidecar isn't up yet; a failed
+/// push is retried the next time the sidecar becomes ready.
+#[tauri::command]
+pub async fn regenerate_device_id(app_handle: tauri::AppHandle) -> Result<String, String> {
+    python_bridge::ensure_writable()?;
+    let _guard = REGENERATE_LOCK.lock().await;
+
+    let device_id = uuid::Uuid::new_v4().to_string();
+    write_local(&app_handle, &device_id)?;
+    println!("Regenerated device ID: {}", device_id);
+
+    if let Err(e) = push(&device_id).await {
+        eprintln!(
+            "Device ID sync failed, will retry when the sidecar is ready: {}",
+            e
+        );
+    }
+    Ok(device_id)
+}
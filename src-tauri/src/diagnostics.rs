@@ -0,0 +1,598 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use futures_util::future::join_all;
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{Manager, State};
+use zip::write::SimpleFileOptions;
+
+use crate::activity;
+use crate::interpreter;
+use crate::paths;
+use crate::python_bridge::{self, PythonSidecar};
+use crate::sidecar_log;
+
+// Read-only endpoints probed by the connection diagnostics screen
+const PING_ENDPOINTS: &[&str] = &["/health", "/today_state", "/api/get_profile"];
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+const SLOW_THRESHOLD_MS: u64 = 500;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointPing {
+    endpoint: String,
+    ok: bool,
+    latency_ms: u64,
+    slow: bool,
+    detail: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PingReport {
+    endpoints: Vec<EndpointPing>,
+    total_ms: u64,
+}
+
+async fn ping(endpoint: &str) -> EndpointPing {
+    let started = Instant::now();
+    let result = python_bridge::probe(endpoint, PING_TIMEOUT).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let (ok, detail) = match result {
+        Ok(status) if status.is_success() => (true, None),
+        Ok(status) => (false, Some(status.to_string())),
+        Err(e) => (false, Some(e)),
+    };
+    EndpointPing {
+        endpoint: endpoint.to_string(),
+        ok,
+        latency_ms,
+        slow: latency_ms > SLOW_THRESHOLD_MS,
+        detail,
+    }
+}
+
+/// Times a GET against each read-only endpoint concurrently.
+#[tauri::command]
+pub async fn ping_endpoints() -> Result<PingReport, String> {
+    let started = Instant::now();
+    let endpoints = join_all(PING_ENDPOINTS.iter().map(|endpoint| ping(endpoint))).await;
+    Ok(PingReport {
+        endpoints,
+        total_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+// Includes a tiny LLM generation, which can be slow on a cold local model
+const SELF_TEST_TIMEOUT: Duration = Duration::from_secs(180);
+// Expected duration per backend check; anything slower is flagged
+const SELF_TEST_EXPECTED_MS: &[(&str, u64)] = &[
+    ("db_read", 500),
+    ("db_write", 1_000),
+    ("classifier", 2_000),
+    ("reflection", 60_000),
+];
+const SELF_TEST_DEFAULT_EXPECTED_MS: u64 = 5_000;
+
+#[derive(Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfTestCheck {
+    name: String,
+    passed: bool,
+    #[serde(default)]
+    detail: Option<String>,
+    #[serde(default, alias = "duration_ms")]
+    duration_ms: u64,
+    /// Took longer than expected; set here, not by the backend
+    #[serde(default, skip_deserializing)]
+    slow: bool,
+}
+
+fn expected_ms(check: &str) -> u64 {
+    SELF_TEST_EXPECTED_MS
+        .iter()
+        .find(|(name, _)| *name == check)
+        .map(|(_, ms)| *ms)
+        .unwrap_or(SELF_TEST_DEFAULT_EXPECTED_MS)
+}
+
+/// Has the backend exercise its core paths end to end, for an "is everything working?" button.
+/// A final "round_trip" entry reports how long the whole run took from the shell's side.
+#[tauri::command]
+pub async fn run_backend_self_test() -> Result<Vec<SelfTestCheck>, String> {
+    let started = Instant::now();
+    let res = python_bridge::post_api_with_timeout(
+        "/api/self_test",
+        serde_json::json!({}),
+        SELF_TEST_TIMEOUT,
+    )
+    .await?;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    let list = res.get("checks").cloned().unwrap_or(res);
+    let mut checks: Vec<SelfTestCheck> =
+        serde_json::from_value(list).map_err(|e| format!("Malformed self-test result: {}", e))?;
+    for check in &mut checks {
+        check.slow = check.duration_ms > expected_ms(&check.name);
+    }
+
+    let budget_ms: u64 = checks.iter().map(|check| expected_ms(&check.name)).sum();
+    checks.push(SelfTestCheck {
+        name: "round_trip".to_string(),
+        passed: true,
+        detail: Some(format!(
+            "{} ms (expected under {} ms)",
+            elapsed_ms, budget_ms
+        )),
+        duration_ms: elapsed_ms,
+        slow: elapsed_ms > budget_ms,
+    });
+    Ok(checks)
+}
+
+// Read-only GETs the debug console may call; anything else is refused
+const DEBUG_ENDPOINTS: &[&str] = &[
+    "/health",
+    "/today_state",
+    "/day_summary",
+    "/api/current",
+    "/api/categories",
+    "/api/stats_range",
+    "/api/recent_events",
+    "/api/excluded_apps",
+    "/api/profiles",
+    "/api/active_profile",
+    "/api/get_profile",
+    "/api/idle_threshold",
+    "/api/timezone",
+    "/api/verify",
+];
+
+/// Raw JSON from a whitelisted endpoint, for support to ask a user for a specific read.
+/// Query strings are passed through. Only works in dev builds or with debug logging on.
+#[tauri::command]
+pub async fn debug_call(endpoint: String) -> Result<serde_json::Value, String> {
+    if !(cfg!(debug_assertions) || sidecar_log::debug_enabled()) {
+        return Err("Debug calls require debug logging to be enabled".to_string());
+    }
+    let path = endpoint.split('?').next().unwrap_or_default();
+    if !DEBUG_ENDPOINTS.contains(&path) {
+        return Err(format!("Endpoint not allowed for debug calls: {}", path));
+    }
+    python_bridge::call_api(&endpoint).await
+}
+
+const AI_HEALTH_TIMEOUT: Duration = Duration::from_secs(4);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AiConnectivity {
+    reachable: bool,
+    // false means the local backend didn't answer, so the provider wasn't checked at all
+    backend_reachable: bool,
+    latency_ms: Option<u64>,
+    detail: String,
+}
+
+/// Asks the backend whether it can reach its model provider, so the reflection screen can
+/// warn before the user writes a prompt.
+#[tauri::command]
+pub async fn check_ai_connectivity() -> Result<AiConnectivity, String> {
+    // Unqueued: a backend that is still starting should be reported as down, not waited on
+    let health = match python_bridge::get_unqueued("/api/ai_health", AI_HEALTH_TIMEOUT).await {
+        Ok(health) => health,
+        Err(e) => {
+            let detail = if python_bridge::is_starting() {
+                "Backend is still starting".to_string()
+            } else {
+                format!("Backend unreachable: {}", e)
+            };
+            return Ok(AiConnectivity {
+                reachable: false,
+                backend_reachable: false,
+                latency_ms: None,
+                detail,
+            });
+        }
+    };
+
+    let reachable = health
+        .get("reachable")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let detail = ["detail", "error"]
+        .iter()
+        .find_map(|key| health.get(key).and_then(|v| v.as_str()))
+        .map(String::from)
+        .unwrap_or_else(|| {
+            if reachable {
+                "AI provider reachable".to_string()
+            } else {
+                "AI provider unreachable".to_string()
+            }
+        });
+    Ok(AiConnectivity {
+        reachable,
+        backend_reachable: true,
+        latency_ms: health.get("latency_ms").and_then(|v| v.as_u64()),
+        detail,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport {
+    ok: bool,
+    // false means the backend couldn't be asked at all, which says nothing about the data itself
+    backend_reachable: bool,
+    issues: Vec<String>,
+}
+
+/// Asks the backend to self-check its data files and adds shell-side checks on top.
+#[tauri::command]
+pub async fn verify_data_integrity(
+    app_handle: tauri::AppHandle,
+) -> Result<IntegrityReport, String> {
+    let mut issues = Vec::new();
+
+    let backend_reachable = match python_bridge::call_api("/api/verify").await {
+        Ok(res) => {
+            if let Some(found) = res.get("issues").and_then(|v| v.as_array()) {
+                issues.extend(found.iter().map(|issue| {
+                    issue
+                        .as_str()
+                        .map(String::from)
+                        .unwrap_or(issue.to_string())
+                }));
+            }
+            if res.get("ok").and_then(|v| v.as_bool()) == Some(false) && issues.is_empty() {
+                issues.push("Backend reported a failed self-check".to_string());
+            }
+            true
+        }
+        Err(e) => {
+            issues.push(format!(
+                "Backend is not reachable, data was not checked: {}",
+                e
+            ));
+            false
+        }
+    };
+
+    match paths::pid_file(&app_handle) {
+        Ok(pid_file) => {
+            if let Some(dir) = pid_file.parent() {
+                if let Err(e) = paths::check_writable(dir) {
+                    issues.push(format!("PID file location: {}", e));
+                }
+            }
+        }
+        Err(e) => issues.push(format!("PID file location: {}", e)),
+    }
+    match paths::backend_data_dir(&app_handle) {
+        Ok(log_dir) => {
+            if let Err(e) = paths::check_writable(&log_dir) {
+                issues.push(format!("Log directory: {}", e));
+            }
+        }
+        Err(e) => issues.push(format!("Log directory: {}", e)),
+    }
+
+    Ok(IntegrityReport {
+        ok: backend_reachable && issues.is_empty(),
+        backend_reachable,
+        issues,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarResourceUsage {
+    cpu_percent: f32,
+    memory_bytes: u64,
+    uptime_secs: u64,
+}
+
+// Kept across calls: CPU usage is measured between two refreshes, and only the sidecar's
+// own PID is ever refreshed so the panel stays cheap
+static PROCESS_STATS: Mutex<Option<(System, Pid)>> = Mutex::new(None);
+
+fn sample_process(pid: u32) -> Option<(f32, u64)> {
+    let pid = Pid::from_u32(pid);
+    let refresh = || ProcessRefreshKind::nothing().with_cpu().with_memory();
+    let mut guard = PROCESS_STATS.lock().unwrap();
+    // A new PID (first call, or the sidecar was restarted) needs a baseline to diff against
+    let baseline = guard.as_ref().is_none_or(|(_, last)| *last != pid);
+    let (system, last) = guard.get_or_insert_with(|| (System::new(), pid));
+    *last = pid;
+
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, refresh());
+    if baseline {
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, refresh());
+    }
+    let process = system.process(pid)?;
+    Some((process.cpu_usage(), process.memory()))
+}
+
+async fn resource_usage(sidecar: &PythonSidecar) -> Result<SidecarResourceUsage, String> {
+    let (pid, started_at) = sidecar
+        .running_since()
+        .ok_or_else(|| "Sidecar is not running".to_string())?;
+
+    let (cpu_percent, memory_bytes) = tokio::task::spawn_blocking(move || sample_process(pid))
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No process stats for sidecar PID {}", pid))?;
+
+    Ok(SidecarResourceUsage {
+        cpu_percent,
+        memory_bytes,
+        uptime_secs: started_at.elapsed().as_secs(),
+    })
+}
+
+#[tauri::command]
+pub async fn get_sidecar_resource_usage(
+    sidecar: State<'_, Arc<PythonSidecar>>,
+) -> Result<SidecarResourceUsage, String> {
+    resource_usage(&sidecar).await
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvironmentCheck {
+    name: &'static str,
+    ok: bool,
+    pub detail: String,
+}
+
+impl EnvironmentCheck {
+    fn new(name: &'static str, result: Result<String, String>) -> Self {
+        let ok = result.is_ok();
+        let detail = result.unwrap_or_else(|e| e);
+        Self { name, ok, detail }
+    }
+
+    /// Checks whose failure means spawning the sidecar can't work.
+    pub fn blocks_startup(&self) -> bool {
+        !self.ok && matches!(self.name, "sidecar" | "data_dir")
+    }
+}
+
+fn python_version() -> Result<String, String> {
+    interpreter::resolve().map(|info| format!("Python {} ({})", info.version, info.path.display()))
+}
+
+fn port_bindable(sidecar: &PythonSidecar) -> Result<String, String> {
+    let endpoint = python_bridge::api_endpoint();
+    if sidecar.running_since().is_some() {
+        return Ok(format!(
+            "Port {} is served by the running backend",
+            endpoint.port
+        ));
+    }
+    std::net::TcpListener::bind((endpoint.host.as_str(), endpoint.port))
+        .map(|_| format!("{}:{} is free", endpoint.host, endpoint.port))
+        .map_err(|e| format!("Cannot bind {}:{}: {}", endpoint.host, endpoint.port, e))
+}
+
+/// Verifies what a local sidecar launch needs, so a failed start can say exactly what's missing.
+pub fn run_environment_checks(
+    app_handle: &tauri::AppHandle,
+    sidecar: &PythonSidecar,
+) -> Vec<EnvironmentCheck> {
+    let mut checks = Vec::new();
+    if python_bridge::api_endpoint().is_remote() {
+        return checks;
+    }
+
+    let resolved = python_bridge::resolve_sidecar_command(app_handle, &[]);
+    let dev_script = matches!(
+        resolved,
+        Ok((_, python_bridge::SidecarSource::DevScript(_)))
+    );
+    checks.push(EnvironmentCheck::new(
+        "sidecar",
+        resolved
+            .map(|(_, source)| source.to_string())
+            .map_err(|e| e.to_string()),
+    ));
+    if dev_script {
+        checks.push(EnvironmentCheck::new("python", python_version()));
+    }
+
+    let data_dir = paths::backend_data_dir(app_handle).and_then(|dir| {
+        std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        paths::check_writable(&dir)?;
+        Ok(dir.display().to_string())
+    });
+    checks.push(EnvironmentCheck::new("data_dir", data_dir));
+    checks.push(EnvironmentCheck::new("port", port_bindable(sidecar)));
+    checks
+}
+
+#[tauri::command]
+pub async fn check_environment(
+    app_handle: tauri::AppHandle,
+    sidecar: State<'_, Arc<PythonSidecar>>,
+) -> Result<Vec<EnvironmentCheck>, String> {
+    let sidecar = sidecar.inner().clone();
+    tokio::task::spawn_blocking(move || run_environment_checks(&app_handle, &sidecar))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    version: String,
+    build_date: &'static str,
+    git_sha: &'static str,
+    tauri_version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    data_dir: Option<String>,
+    sidecar_source: Option<python_bridge::SidecarSourceInfo>,
+    /// host:port requests are sent to, after the IPv4/IPv6 fallback
+    api_address: String,
+}
+
+/// Version and build details for the About screen and support. Works with the backend down.
+#[tauri::command]
+pub fn get_app_info(app_handle: tauri::AppHandle) -> AppInfo {
+    AppInfo {
+        version: app_handle.package_info().version.to_string(),
+        build_date: env!("OVELO_BUILD_DATE"),
+        git_sha: env!("OVELO_GIT_SHA"),
+        tauri_version: tauri::VERSION,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        data_dir: paths::backend_data_dir(&app_handle)
+            .ok()
+            .map(|dir| dir.display().to_string()),
+        sidecar_source: app_handle.state::<Arc<PythonSidecar>>().source_info(),
+        api_address: {
+            let endpoint = python_bridge::active_endpoint();
+            endpoint.url("").trim_start_matches("http://").to_string()
+        },
+    }
+}
+
+#[tauri::command]
+pub fn get_sidecar_source(
+    sidecar: State<'_, Arc<PythonSidecar>>,
+) -> Option<python_bridge::SidecarSourceInfo> {
+    sidecar.source_info()
+}
+
+// Never bundled, whatever else changes
+const BUNDLE_EXCLUDED: &[&str] = &[
+    "focus data (timeline, events, window titles)",
+    "reflections",
+    "profile and settings",
+    "backups",
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    created_at: String,
+    included: Vec<String>,
+    excluded: Vec<String>,
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+// Strips the home dir and account name so paths in logs don't identify the user
+fn redact_user(text: &str, home: Option<&str>, user: Option<&str>) -> String {
+    let mut text = text.to_string();
+    if let Some(home) = home.filter(|h| h.len() > 1) {
+        text = text.replace(home, "~");
+    }
+    if let Some(user) = user.filter(|u| u.len() > 2) {
+        text = text.replace(user, "<user>");
+    }
+    text
+}
+
+/// Zips logs plus system and app diagnostics for a support ticket. Personal data is never
+/// included; the bundle's manifest.json lists exactly what is and isn't in it.
+#[tauri::command]
+pub async fn create_diagnostic_bundle(
+    app_handle: tauri::AppHandle,
+    sidecar: State<'_, Arc<PythonSidecar>>,
+    dest_path: String,
+) -> Result<BundleManifest, String> {
+    let sidecar = sidecar.inner().clone();
+    let home = app_handle
+        .path()
+        .home_dir()
+        .ok()
+        .map(|dir| dir.display().to_string());
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .ok();
+    let redact = move |text: &str| redact_user(text, home.as_deref(), user.as_deref());
+
+    let mut files: Vec<(String, String)> = Vec::new();
+    let mut excluded: Vec<String> = BUNDLE_EXCLUDED.iter().map(|s| s.to_string()).collect();
+
+    files.push((
+        "app_info.json".into(),
+        to_json(&get_app_info(app_handle.clone())),
+    ));
+    let checks = {
+        let (app_handle, sidecar) = (app_handle.clone(), sidecar.clone());
+        tokio::task::spawn_blocking(move || run_environment_checks(&app_handle, &sidecar))
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    files.push(("environment.json".into(), to_json(&checks)));
+    match resource_usage(&sidecar).await {
+        Ok(usage) => files.push(("resource_usage.json".into(), to_json(&usage))),
+        Err(e) => excluded.push(format!("resource usage ({})", e)),
+    }
+    match activity::fetch_classifier_info().await {
+        Ok(info) => files.push(("classifier.json".into(), to_json(&info))),
+        Err(e) => excluded.push(format!("classifier info ({})", e)),
+    }
+    files.push((
+        "sidecar_errors.txt".into(),
+        sidecar.recent_errors().join("\n"),
+    ));
+    match sidecar.log().path() {
+        Some(path) => match tokio::fs::read(&path).await {
+            Ok(raw) => files.push((
+                "sidecar.log".into(),
+                String::from_utf8_lossy(&raw).into_owned(),
+            )),
+            Err(e) => excluded.push(format!("sidecar log ({})", e)),
+        },
+        None => excluded.push("sidecar log (not open)".to_string()),
+    }
+    // The shell logs to stdout only; there's no file to include
+    excluded.push("shell log (console output only)".to_string());
+
+    let mut manifest = BundleManifest {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        included: files.iter().map(|(name, _)| name.clone()).collect(),
+        excluded,
+    };
+    manifest.included.push("manifest.json".to_string());
+    let manifest_json = to_json(&manifest);
+
+    let dest = PathBuf::from(dest_path);
+    let written = tokio::task::spawn_blocking({
+        let dest = dest.clone();
+        move || -> Result<(), String> {
+            let file = std::fs::File::create(&dest).map_err(|e| e.to_string())?;
+            let mut zip = zip::ZipWriter::new(file);
+            let options =
+                SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+            let manifest = ("manifest.json".to_string(), manifest_json);
+            for (name, contents) in files.iter().chain(std::iter::once(&manifest)) {
+                zip.start_file(name.as_str(), options)
+                    .map_err(|e| e.to_string())?;
+                zip.write_all(redact(contents).as_bytes())
+                    .map_err(|e| e.to_string())?;
+            }
+            zip.finish().map_err(|e| e.to_string())?;
+            Ok(())
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&dest);
+        return Err(format!("Failed to write diagnostic bundle: {}", e));
+    }
+    Ok(manifest)
+}
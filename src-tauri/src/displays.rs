@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::Emitter;
+
+// Tauri has no monitor hotplug event, so connections are picked up by polling
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayBounds {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Display {
+    id: String,
+    name: String,
+    bounds: DisplayBounds,
+    is_primary: bool,
+}
+
+fn list(app_handle: &tauri::AppHandle) -> Result<Vec<Display>, String> {
+    let monitors = app_handle.available_monitors().map_err(|e| e.to_string())?;
+    let primary = app_handle.primary_monitor().ok().flatten();
+
+    Ok(monitors
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| {
+            let position = monitor.position();
+            let size = monitor.size();
+            let name = monitor
+                .name()
+                .cloned()
+                .unwrap_or_else(|| format!("Display {}", index + 1));
+            let is_primary = primary.as_ref().is_some_and(|p| {
+                p.name() == monitor.name() && p.position() == position && p.size() == size
+            });
+            Display {
+                // Monitor names aren't unique (two identical panels), so the origin is part of the id
+                id: format!("{}@{},{}", name, position.x, position.y),
+                name,
+                bounds: DisplayBounds {
+                    x: position.x,
+                    y: position.y,
+                    width: size.width,
+                    height: size.height,
+                },
+                is_primary,
+            }
+        })
+        .collect())
+}
+
+/// Emits `displays-changed` with the new list whenever a monitor is connected, removed or moved.
+pub fn start_watcher(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last = list(&app_handle).ok();
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let Ok(current) = list(&app_handle) else {
+                continue;
+            };
+            if last.as_ref() != Some(&current) {
+                let _ = app_handle.emit("displays-changed", &current);
+                last = Some(current);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_displays(app_handle: tauri::AppHandle) -> Result<Vec<Display>, String> {
+    list(&app_handle)
+}
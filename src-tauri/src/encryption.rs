@@ -0,0 +1,98 @@
+use std::time::{Duration, Instant};
+
+use zeroize::Zeroizing;
+
+use crate::python_bridge;
+
+const MIN_PASSPHRASE_CHARS: usize = 8;
+// Wrong passphrases allowed before unlocking starts backing off
+const FREE_ATTEMPTS: u32 = 3;
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+struct UnlockAttempts {
+    failures: u32,
+    retry_after: Option<Instant>,
+}
+
+// Held from the backoff check until the outcome is recorded, so concurrent unlocks queue up
+// behind each other instead of all slipping past the same check
+static ATTEMPTS: tokio::sync::Mutex<UnlockAttempts> =
+    tokio::sync::Mutex::const_new(UnlockAttempts {
+        failures: 0,
+        retry_after: None,
+    });
+
+// Doubles the wait with each failure past the free ones: 2s, 4s, 8s, ... up to MAX_BACKOFF
+fn backoff(failures: u32) -> Option<Duration> {
+    let over = failures.checked_sub(FREE_ATTEMPTS)?;
+    Some(Duration::from_secs(2u64.saturating_pow(over + 1)).min(MAX_BACKOFF))
+}
+
+// The `Zeroizing` wrapper wipes our copy on drop. The request body serde_json and reqwest
+// build from it can't be reached to wipe, so never keep it around or log it.
+async fn post_passphrase(
+    endpoint: &str,
+    passphrase: &Zeroizing<String>,
+) -> Result<serde_json::Value, String> {
+    python_bridge::post_api(
+        endpoint,
+        serde_json::json!({ "passphrase": passphrase.as_str() }),
+    )
+    .await
+}
+
+/// Turns on encryption at rest (or changes the passphrase while unlocked).
+#[tauri::command]
+pub async fn set_data_passphrase(passphrase: String) -> Result<(), String> {
+    let passphrase = Zeroizing::new(passphrase);
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_CHARS
+        ));
+    }
+    post_passphrase("/api/set_passphrase", &passphrase).await?;
+    println!("Data passphrase updated");
+    Ok(())
+}
+
+/// Unlocks the backend's data. Repeated wrong passphrases are slowed down here
+/// so a script driving the UI can't brute-force it quickly.
+#[tauri::command]
+pub async fn unlock_data(passphrase: String) -> Result<bool, String> {
+    let passphrase = Zeroizing::new(passphrase);
+    let mut attempts = ATTEMPTS.lock().await;
+    if let Some(retry_after) = attempts.retry_after {
+        let now = Instant::now();
+        if retry_after > now {
+            return Err(format!(
+                "Too many failed attempts, try again in {} s",
+                (retry_after - now).as_secs() + 1
+            ));
+        }
+    }
+
+    let res = post_passphrase("/api/unlock", &passphrase).await?;
+    let unlocked = res
+        .get("unlocked")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
+    if unlocked {
+        attempts.failures = 0;
+        attempts.retry_after = None;
+    } else {
+        attempts.failures += 1;
+        attempts.retry_after = backoff(attempts.failures).map(|wait| Instant::now() + wait);
+        println!("Unlock failed ({} consecutive)", attempts.failures);
+    }
+    Ok(unlocked)
+}
+
+#[tauri::command]
+pub async fn is_data_locked() -> Result<bool, String> {
+    let res = python_bridge::call_api("/api/encryption_status").await?;
+    res.get("locked")
+        .and_then(|v| v.as_bool())
+        .ok_or_else(|| "Malformed encryption status".to_string())
+}
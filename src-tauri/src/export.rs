@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+use tokio::io::{AsyncWriteExt, BufWriter};
+
+use crate::python_bridge;
+use crate::reflections;
+
+// Emit progress at most once per this many bytes (plus a final event)
+const PROGRESS_STEP_BYTES: u64 = 256 * 1024;
+
+#[derive(Clone, Serialize)]
+struct ExportProgress {
+    written: u64,
+    // None when the server didn't send Content-Length (indeterminate progress)
+    total: Option<u64>,
+}
+
+async fn write_stream(
+    app_handle: &tauri::AppHandle,
+    res: reqwest::Response,
+    dest: &Path,
+) -> Result<u64, String> {
+    let total = res.content_length();
+    let mut file = tokio::fs::File::create(dest)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut stream = res.bytes_stream();
+    let mut written = 0u64;
+    let mut last_emitted = 0u64;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        file.write_all(&chunk).await.map_err(|e| e.to_string())?;
+        written += chunk.len() as u64;
+        if written - last_emitted >= PROGRESS_STEP_BYTES {
+            let _ = app_handle.emit("export-progress", ExportProgress { written, total });
+            last_emitted = written;
+        }
+    }
+    file.flush().await.map_err(|e| e.to_string())?;
+
+    let _ = app_handle.emit("export-progress", ExportProgress { written, total });
+    Ok(written)
+}
+
+/// Streams the backend's data export straight to `dest_path`, returning the bytes written.
+/// A failed transfer removes the partial file rather than leaving a corrupt archive behind.
+#[tauri::command]
+pub async fn export_data(app_handle: tauri::AppHandle, dest_path: String) -> Result<u64, String> {
+    let dest = PathBuf::from(dest_path);
+    let res = python_bridge::get_raw("/api/export").await?;
+
+    match write_stream(&app_handle, res, &dest).await {
+        Ok(written) => Ok(written),
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&dest).await;
+            Err(format!("Export failed: {}", e))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct DayStats {
+    date: String,
+    #[serde(default)]
+    focus_minutes: f64,
+    #[serde(default)]
+    distraction_minutes: f64,
+    #[serde(default)]
+    total_minutes: f64,
+}
+
+const CSV_HEADER: &str = "date,focus_minutes,distraction_minutes,total_minutes\n";
+
+// Quotes a field if it contains a delimiter, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+async fn write_csv(dest: &Path, days: Vec<DayStats>) -> Result<usize, String> {
+    let file = tokio::fs::File::create(dest)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut out = BufWriter::new(file);
+    out.write_all(CSV_HEADER.as_bytes())
+        .await
+        .map_err(|e| e.to_string())?;
+    for day in &days {
+        let row = format!(
+            "{},{},{},{}\n",
+            csv_field(&day.date),
+            day.focus_minutes,
+            day.distraction_minutes,
+            day.total_minutes
+        );
+        out.write_all(row.as_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    out.flush().await.map_err(|e| e.to_string())?;
+    Ok(days.len())
+}
+
+/// Writes one CSV row per day in [start, end] to `dest_path`, returning the number of rows.
+#[tauri::command]
+pub async fn export_stats_csv(
+    start: String,
+    end: String,
+    dest_path: String,
+) -> Result<usize, String> {
+    reflections::parse_date_range(&start, &end)?;
+    let res =
+        python_bridge::call_api(&format!("/api/stats_range?start={}&end={}", start, end)).await?;
+    let days = res.get("days").cloned().unwrap_or(res);
+    let days: Vec<DayStats> =
+        serde_json::from_value(days).map_err(|e| format!("Malformed stats response: {}", e))?;
+
+    let dest = PathBuf::from(dest_path);
+    match write_csv(&dest, days).await {
+        Ok(rows) => Ok(rows),
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&dest).await;
+            Err(format!("CSV export failed: {}", e))
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::local_store::LocalStore;
+use crate::notifications;
+use crate::today_stream;
+use crate::webhook;
+
+const CONFIG_KEY: &str = "focus_alerts";
+const MILESTONE_RANGE: std::ops::RangeInclusive<u32> = 5..=240;
+// Timeline states the analyzer counts as focused
+pub(crate) const FOCUS_STATES: &[&str] = &["Focus Peak", "Light Focus"];
+// Leaving focus only ends a session once it has lasted this long, so flicker doesn't spam
+const INTERRUPT_DEBOUNCE_SECS: f64 = 120.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FocusAlertConfig {
+    pub enabled: bool,
+    pub milestone_minutes: u32,
+}
+
+impl Default for FocusAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            milestone_minutes: 25,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FocusSessionEvent {
+    status: &'static str,
+    minutes: u32,
+}
+
+/// Latest timeline point as (timestamp, focused) plus the start of the trailing focus run.
+fn latest_point(state: &serde_json::Value) -> Option<(f64, bool, Option<f64>)> {
+    let timeline = state.get("timeline")?.as_array()?;
+    let is_focus = |point: &serde_json::Value| {
+        point
+            .get("state")
+            .and_then(|s| s.as_str())
+            .is_some_and(|s| FOCUS_STATES.contains(&s))
+    };
+    let timestamp = |point: &serde_json::Value| point.get("timestamp").and_then(|t| t.as_f64());
+
+    let last = timeline.last()?;
+    let focused = is_focus(last);
+    let run_start = timeline
+        .iter()
+        .rev()
+        .take_while(|point| is_focus(point))
+        .last()
+        .and_then(timestamp);
+    Some((timestamp(last)?, focused, run_start))
+}
+
+#[derive(Default)]
+struct SessionTracker {
+    started_at: Option<f64>,
+    milestones: u32,
+    unfocused_since: Option<f64>,
+}
+
+impl SessionTracker {
+    fn update(
+        &mut self,
+        app_handle: &tauri::AppHandle,
+        config: &FocusAlertConfig,
+        state: &serde_json::Value,
+    ) {
+        let Some((now, focused, run_start)) = latest_point(state) else {
+            return;
+        };
+
+        if focused {
+            self.unfocused_since = None;
+            // A short dip keeps the original session; otherwise the trailing run starts a new one
+            if self.started_at.is_none() {
+                self.started_at = Some(run_start.unwrap_or(now));
+                let _ = app_handle.emit(
+                    "focus-session",
+                    FocusSessionEvent {
+                        status: "started",
+                        minutes: 0,
+                    },
+                );
+                webhook::dispatch(app_handle, "focus_started", 0);
+            }
+            let started_at = self.started_at.unwrap_or(now);
+            let minutes = ((now - started_at) / 60.0).max(0.0) as u32;
+            let reached = minutes / config.milestone_minutes.max(1);
+            if reached > self.milestones {
+                self.milestones = reached;
+                let minutes = reached * config.milestone_minutes;
+                self.announce(app_handle, config, "milestone", minutes);
+            }
+        } else if let Some(started_at) = self.started_at {
+            let since = *self.unfocused_since.get_or_insert(now);
+            if now - since >= INTERRUPT_DEBOUNCE_SECS {
+                let minutes = ((since - started_at) / 60.0).max(0.0) as u32;
+                self.announce(app_handle, config, "ended", minutes);
+                *self = SessionTracker::default();
+            }
+        }
+    }
+
+    fn announce(
+        &self,
+        app_handle: &tauri::AppHandle,
+        config: &FocusAlertConfig,
+        status: &'static str,
+        minutes: u32,
+    ) {
+        let _ = app_handle.emit("focus-session", FocusSessionEvent { status, minutes });
+        webhook::dispatch(app_handle, &format!("focus_{}", status), minutes);
+        // Every session's end is reported, but only ones that reached a milestone are worth
+        // a notification
+        if !config.enabled || (status == "ended" && self.milestones == 0) {
+            return;
+        }
+        match status {
+            "milestone" => notifications::notify(
+                app_handle,
+                "Ovelo",
+                &format!("You've been focused for {} minutes 🎯", minutes),
+            ),
+            _ => notifications::notify(app_handle, "Ovelo", "Focus interrupted"),
+        }
+    }
+}
+
+fn config(app_handle: &tauri::AppHandle) -> FocusAlertConfig {
+    app_handle
+        .state::<LocalStore>()
+        .get(CONFIG_KEY)
+        .unwrap_or_default()
+}
+
+/// Watches today_state updates for focus-session transitions.
+pub fn start_watcher(app_handle: tauri::AppHandle) {
+    let mut updates = today_stream::subscribe();
+    tauri::async_runtime::spawn(async move {
+        let mut tracker = SessionTracker::default();
+        loop {
+            match updates.recv().await {
+                Ok(state) => tracker.update(&app_handle, &config(&app_handle), &state),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn configure_focus_alerts(
+    app_handle: tauri::AppHandle,
+    config: FocusAlertConfig,
+) -> Result<FocusAlertConfig, String> {
+    if !MILESTONE_RANGE.contains(&config.milestone_minutes) {
+        return Err(format!(
+            "Milestone must be between {} and {} minutes",
+            MILESTONE_RANGE.start(),
+            MILESTONE_RANGE.end()
+        ));
+    }
+    app_handle.state::<LocalStore>().set(CONFIG_KEY, &config)?;
+    Ok(config)
+}
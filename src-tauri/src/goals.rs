@@ -0,0 +1,109 @@
+use chrono::{Local, NaiveDate, TimeZone};
+use tauri::Manager;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::focus_alerts::FOCUS_STATES;
+use crate::local_store::LocalStore;
+use crate::notifications;
+use crate::today_stream;
+
+const GOAL_KEY: &str = "daily_goal_minutes";
+// Day the goal notification last fired, so it's sent once per local day
+const NOTIFIED_KEY: &str = "daily_goal_notified_on";
+const MAX_GOAL_MINUTES: u32 = 16 * 60;
+// Longer gaps between samples are tracking pauses, not focus
+pub const MAX_SAMPLE_GAP_SECS: f64 = 300.0;
+
+/// Unix seconds of the most recent local midnight.
+pub fn local_midnight() -> f64 {
+    Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|dt| dt.timestamp() as f64)
+        .unwrap_or(0.0)
+}
+
+// Focused minutes since local midnight, from the timeline's sample spacing
+fn focus_minutes_today(state: &serde_json::Value) -> f64 {
+    let Some(timeline) = state.get("timeline").and_then(|t| t.as_array()) else {
+        return 0.0;
+    };
+    let midnight = local_midnight();
+
+    let points: Vec<(f64, bool)> = timeline
+        .iter()
+        .filter_map(|point| {
+            let timestamp = point.get("timestamp")?.as_f64()?;
+            let focused = point
+                .get("state")
+                .and_then(|s| s.as_str())
+                .is_some_and(|s| FOCUS_STATES.contains(&s));
+            Some((timestamp, focused))
+        })
+        .filter(|(timestamp, _)| *timestamp >= midnight)
+        .collect();
+
+    let seconds: f64 = points
+        .windows(2)
+        .filter(|pair| pair[0].1)
+        .map(|pair| (pair[1].0 - pair[0].0).clamp(0.0, MAX_SAMPLE_GAP_SECS))
+        .sum();
+    seconds / 60.0
+}
+
+fn check_goal(app_handle: &tauri::AppHandle, state: &serde_json::Value) {
+    let store = app_handle.state::<LocalStore>();
+    let Some(goal) = store.get::<u32>(GOAL_KEY) else {
+        return;
+    };
+    let today = Local::now().date_naive();
+    // A date from a previous day means the flag reset at midnight
+    if store.get::<NaiveDate>(NOTIFIED_KEY) == Some(today) {
+        return;
+    }
+    if focus_minutes_today(state) >= goal as f64 {
+        if let Err(e) = store.set(NOTIFIED_KEY, today) {
+            eprintln!("Failed to record daily goal: {}", e);
+        }
+        notifications::notify(
+            app_handle,
+            "Ovelo",
+            &format!("Daily focus goal reached: {} minutes 🎉", goal),
+        );
+    }
+}
+
+/// Watches today_state updates for the daily focus goal being crossed.
+pub fn start_watcher(app_handle: tauri::AppHandle) {
+    let mut updates = today_stream::subscribe();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match updates.recv().await {
+                Ok(state) => check_goal(&app_handle, &state),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_daily_goal(app_handle: tauri::AppHandle) -> Option<u32> {
+    app_handle.state::<LocalStore>().get(GOAL_KEY)
+}
+
+/// Sets today's focus target in minutes; 0 turns the goal off.
+#[tauri::command]
+pub fn set_daily_goal(app_handle: tauri::AppHandle, minutes: u32) -> Result<Option<u32>, String> {
+    let store = app_handle.state::<LocalStore>();
+    if minutes == 0 {
+        store.remove(GOAL_KEY)?;
+        return Ok(None);
+    }
+    if minutes > MAX_GOAL_MINUTES {
+        return Err(format!("Goal can be at most {} minutes", MAX_GOAL_MINUTES));
+    }
+    store.set(GOAL_KEY, minutes)?;
+    Ok(Some(minutes))
+}
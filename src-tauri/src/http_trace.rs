@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tauri::Manager;
+
+use crate::local_store::LocalStore;
+
+const TRACE_KEY: &str = "http_trace";
+const MAX_BODY_CHARS: usize = 512;
+// JSON body fields whose values never reach the log, matched as substrings of the key
+// (so `passphrase` also covers `newPassphrase`)
+const SECRET_FIELDS: &[&str] = &["passphrase", "password", "secret", "token", "confirmation"];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Method, path, headers and truncated body of an outgoing request, captured before it's sent.
+pub struct TracedRequest(String);
+
+fn redact_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if SECRET_FIELDS.iter().any(|field| key.contains(field)) {
+                    *value = serde_json::Value::String("<redacted>".to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+// Bodies are JSON; anything that doesn't parse is left out rather than risk logging a secret
+fn body_text(bytes: &[u8]) -> String {
+    match serde_json::from_slice::<serde_json::Value>(bytes) {
+        Ok(mut value) => {
+            redact_secrets(&mut value);
+            value.to_string()
+        }
+        Err(_) => format!("<{} bytes, not JSON>", bytes.len()),
+    }
+}
+
+pub fn describe(request: &reqwest::Request) -> TracedRequest {
+    let headers: Vec<String> = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            if name == reqwest::header::AUTHORIZATION {
+                format!("{}: <redacted>", name)
+            } else {
+                format!("{}: {}", name, value.to_str().unwrap_or("<binary>"))
+            }
+        })
+        .collect();
+    let body = request
+        .body()
+        .and_then(|body| body.as_bytes())
+        .map(|bytes| {
+            let text = body_text(bytes);
+            let mut truncated: String = text.chars().take(MAX_BODY_CHARS).collect();
+            if text.chars().count() > MAX_BODY_CHARS {
+                truncated.push_str("...");
+            }
+            truncated
+        });
+
+    let url = request.url();
+    let path = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    let mut line = format!("{} {} [{}]", request.method(), path, headers.join(", "));
+    if let Some(body) = body {
+        line.push_str(&format!(" body={}", body));
+    }
+    TracedRequest(line)
+}
+
+pub fn log(request: TracedRequest, result: &Result<reqwest::Response, String>, latency: Duration) {
+    let outcome = match result {
+        Ok(res) => res.status().to_string(),
+        Err(e) => format!("failed: {}", e),
+    };
+    println!(
+        "[http] {} -> {} in {} ms",
+        request.0,
+        outcome,
+        latency.as_millis()
+    );
+}
+
+/// Turns the trace back on if the user left it enabled; called from the setup hook.
+pub fn restore_http_trace(app_handle: &tauri::AppHandle) {
+    if app_handle.state::<LocalStore>().get::<bool>(TRACE_KEY) == Some(true) {
+        ENABLED.store(true, Ordering::Relaxed);
+        println!("HTTP tracing is on");
+    }
+}
+
+/// Logs every request to the sidecar (method, path, status, latency, truncated body with
+/// secret fields redacted).
+#[tauri::command]
+pub fn set_http_trace(app_handle: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    app_handle.state::<LocalStore>().set(TRACE_KEY, enabled)?;
+    println!(
+        "HTTP tracing {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(enabled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_text_redacts_secret_fields() {
+        let body = br#"{"passphrase":"hunter22","nested":{"newPassphrase":"x"},"confirmation":"DELETE ALL MY DATA","date":"2024-05-01"}"#;
+        let text = body_text(body);
+        assert!(!text.contains("hunter22"));
+        assert!(!text.contains("DELETE ALL MY DATA"));
+        assert!(!text.contains("\"x\""));
+        assert!(text.contains("2024-05-01"));
+    }
+
+    #[test]
+    fn body_text_skips_non_json() {
+        assert_eq!(body_text(b"passphrase=hunter22"), "<19 bytes, not JSON>");
+    }
+}
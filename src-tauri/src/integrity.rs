@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+pub use crate::checksum::sha256_file;
+
+// Hash of resources/backend/ovelo_server recorded by build.rs; empty if it wasn't built yet
+const EXPECTED_SIDECAR_SHA256: &str = env!("OVELO_SIDECAR_SHA256");
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityFailure {
+    pub path: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// Checks a bundled sidecar against the hash embedded at build time.
+pub fn verify_bundled(path: &Path) -> Result<(), IntegrityFailure> {
+    if EXPECTED_SIDECAR_SHA256.is_empty() {
+        println!("No embedded sidecar checksum, skipping integrity check");
+        return Ok(());
+    }
+    let actual = sha256_file(path).unwrap_or_else(|e| format!("unreadable: {}", e));
+    if actual == EXPECTED_SIDECAR_SHA256 {
+        Ok(())
+    } else {
+        Err(IntegrityFailure {
+            path: path.display().to_string(),
+            expected: EXPECTED_SIDECAR_SHA256.to_string(),
+            actual,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_file_matches_known_digests() {
+        let dir = std::env::temp_dir().join(format!("ovelo-sha256-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (empty, abc) = (dir.join("empty"), dir.join("abc"));
+        std::fs::write(&empty, b"").unwrap();
+        std::fs::write(&abc, b"abc").unwrap();
+
+        let digests = (sha256_file(&empty), sha256_file(&abc));
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            digests.0.unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            digests.1.unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn sha256_file_reports_missing_files() {
+        assert!(sha256_file(Path::new("/nonexistent/ovelo_server")).is_err());
+    }
+}
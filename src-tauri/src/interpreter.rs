@@ -0,0 +1,88 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+// Oldest interpreter the dev backend is known to run on
+const MIN_PYTHON: (u32, u32) = (3, 8);
+
+// Tried in order when OVELO_PYTHON isn't set; `python` is often Python 2 on older setups
+const CANDIDATES: &[&str] = &["python3", "python"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PythonInfo {
+    pub path: PathBuf,
+    pub version: String,
+}
+
+// Runs `<program> --version` and parses "Python 3.11.4" (older interpreters print it to stderr)
+fn probe(program: &Path) -> Result<String, String> {
+    let output = std::process::Command::new(program)
+        .arg("--version")
+        .output()
+        .map_err(|e| format!("{:?} could not be run: {}", program, e))?;
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let version = text.trim().trim_start_matches("Python ").to_string();
+    let mut parts = version.split('.').filter_map(|p| p.parse::<u32>().ok());
+    match (parts.next(), parts.next()) {
+        (Some(major), Some(minor)) if (major, minor) >= MIN_PYTHON => Ok(version),
+        (Some(_), Some(_)) => Err(format!(
+            "{:?} is Python {}, {}.{}+ required",
+            program, version, MIN_PYTHON.0, MIN_PYTHON.1
+        )),
+        _ => Err(format!(
+            "{:?} printed unrecognized version output: {}",
+            program,
+            text.trim()
+        )),
+    }
+}
+
+/// Picks the interpreter for the dev sidecar: `OVELO_PYTHON` if set (no fallback, so a bad
+/// override is reported rather than silently ignored), else the first compatible candidate.
+pub fn resolve() -> Result<PythonInfo, String> {
+    if let Some(path) = std::env::var_os("OVELO_PYTHON").map(PathBuf::from) {
+        return match probe(&path) {
+            Ok(version) => {
+                println!("Python: using OVELO_PYTHON {:?} ({})", path, version);
+                Ok(PythonInfo { path, version })
+            }
+            Err(e) => {
+                eprintln!("Python: OVELO_PYTHON rejected: {}", e);
+                Err(format!("OVELO_PYTHON: {}", e))
+            }
+        };
+    }
+
+    let mut rejected = Vec::new();
+    for candidate in CANDIDATES {
+        let path = PathBuf::from(candidate);
+        match probe(&path) {
+            Ok(version) => {
+                println!("Python: using {:?} ({})", path, version);
+                return Ok(PythonInfo { path, version });
+            }
+            Err(e) => {
+                println!("Python: skipping {}", e);
+                rejected.push(e);
+            }
+        }
+    }
+    let error = format!(
+        "No compatible Python found (set OVELO_PYTHON to override): {}",
+        rejected.join("; ")
+    );
+    eprintln!("{}", error);
+    Err(error)
+}
+
+#[tauri::command]
+pub async fn get_python_info() -> Result<PythonInfo, String> {
+    tokio::task::spawn_blocking(resolve)
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use tauri::Manager;
+
+use crate::local_store::LocalStore;
+use crate::python_bridge::{self, PythonSidecar};
+
+const LAZY_KEY: &str = "lazy_sidecar";
+// Overrides the saved preference either way ("1"/"true" or "0"/"false")
+const LAZY_ENV: &str = "OVELO_LAZY_SIDECAR";
+
+/// Whether this launch should leave the sidecar stopped until it's first needed.
+pub fn enabled(app_handle: &tauri::AppHandle) -> bool {
+    match std::env::var(LAZY_ENV).ok().as_deref().map(str::trim) {
+        Some("1") | Some("true") => true,
+        Some("0") | Some("false") => false,
+        _ => app_handle
+            .state::<LocalStore>()
+            .get::<bool>(LAZY_KEY)
+            .unwrap_or(false),
+    }
+}
+
+/// Defers the sidecar and starts it on the first request for it: the main window gaining
+/// focus, or a command that calls the backend. A window already shown at launch (not
+/// started minimized, or onboarding) counts as that first request.
+pub fn spawn(app_handle: tauri::AppHandle, sidecar: Arc<PythonSidecar>) {
+    python_bridge::defer_start();
+    println!("Lazy start: the Python sidecar starts when first needed");
+    let shown = app_handle
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false);
+    if shown {
+        python_bridge::request_start();
+    }
+
+    tauri::async_runtime::spawn(async move {
+        python_bridge::start_request().await;
+        sidecar.start_async(&app_handle).await;
+        crate::spawn_ready_watch(app_handle, sidecar);
+    });
+}
+
+/// Starts a deferred sidecar ahead of need (e.g. when the user heads for a screen that uses it)
+/// and waits until it's ready. Returns at once when it's already up.
+#[tauri::command]
+pub async fn ensure_sidecar_started(
+    sidecar: tauri::State<'_, Arc<PythonSidecar>>,
+) -> Result<(), String> {
+    sidecar.ensure_started().await
+}
+
+/// Trades first-interaction latency for a lighter launch. Takes effect on the next start.
+#[tauri::command]
+pub fn set_lazy_start(app_handle: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
+    app_handle.state::<LocalStore>().set(LAZY_KEY, enabled)?;
+    Ok(enabled)
+}
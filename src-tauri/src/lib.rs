@@ -1,18 +1,165 @@
+mod activity;
+mod app_limits;
+mod auto_pause;
+mod backup;
+mod cache;
+mod checksum;
+mod clock;
+mod device;
+mod diagnostics;
+mod displays;
+mod encryption;
+mod export;
+mod focus_alerts;
+mod goals;
+mod http_trace;
+mod integrity;
+mod interpreter;
+mod lazy_start;
+mod local_store;
+mod main_window;
+mod migration;
+mod notifications;
+mod onboarding;
+mod paths;
+mod permissions;
+mod personas;
+mod power;
+mod profiles;
 mod python_bridge;
+mod reclassify;
+mod reflection_stream;
+mod reflections;
+mod reminders;
+mod retention;
+mod runtime_files;
+mod settings;
+mod shortcuts;
+mod sidecar_log;
+mod startup_metrics;
+mod status_file;
+mod storage;
+mod sync;
+mod timezone;
+mod today_stream;
+mod tray;
+mod updates;
+mod webhook;
+use local_store::LocalStore;
 use python_bridge::PythonSidecar;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tauri::State;
 use tauri::{
-    menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager,
+    Emitter, Manager,
 };
 
+// Accepted idle-detection thresholds, in seconds
+const IDLE_THRESHOLD_RANGE: std::ops::RangeInclusive<u32> = 30..=3600;
+
+// How long quitting waits for in-flight saves before the sidecar is killed
+const EXIT_DRAIN_TIMEOUT: Duration = Duration::from_secs(3);
+
+// LLM prewarm: fired a little after the sidecar comes up so it stays off the startup path
+const PREWARM_DELAY: Duration = Duration::from_secs(5);
+const PREWARM_TIMEOUT: Duration = Duration::from_secs(120);
+
+// Fire-and-forget; prewarming is an optimization, so failures are only logged at debug verbosity
+fn spawn_prewarm(delay: Duration) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(delay).await;
+        let started = Instant::now();
+        let result = python_bridge::post_api_untracked(
+            "/api/prewarm",
+            serde_json::json!({}),
+            PREWARM_TIMEOUT,
+        )
+        .await;
+        if cfg!(debug_assertions) || sidecar_log::debug_enabled() {
+            match result {
+                Ok(_) => println!("LLM prewarm took {} ms", started.elapsed().as_millis()),
+                Err(e) => println!("LLM prewarm failed: {}", e),
+            }
+        }
+    });
+}
+
+// Loads what the reflection and settings screens read first into the TTL cache, so their
+// first navigation doesn't wait on the backend. Daily goals live in the LocalStore already.
+fn spawn_warm_caches() {
+    tauri::async_runtime::spawn(async move {
+        let started = Instant::now();
+        let (personas, settings) =
+            tokio::join!(personas::fetch_personas(), settings::fetch_settings());
+        for (name, error) in [("personas", personas.err()), ("settings", settings.err())] {
+            if let Some(e) = error {
+                eprintln!("Cache warm-up for {} failed: {}", name, e);
+            }
+        }
+        if cfg!(debug_assertions) || sidecar_log::debug_enabled() {
+            println!("Cache warm-up took {} ms", started.elapsed().as_millis());
+        }
+    });
+}
+
+// Recompute rescans the whole day's events
+const RECOMPUTE_TIMEOUT: Duration = Duration::from_secs(60);
+
+// Flag passed by the autostart plugin
+const MINIMIZED_ARG: &str = "--minimized";
+
+/// Process arguments captured at startup.
+struct LaunchArgs {
+    minimized: bool,
+}
+
+impl LaunchArgs {
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Self {
+        Self {
+            minimized: args.into_iter().skip(1).any(|arg| arg == MINIMIZED_ARG),
+        }
+    }
+}
+
+// Waits for the sidecar in the background and tells the frontend once it's reachable
+pub(crate) fn spawn_ready_watch(app_handle: tauri::AppHandle, sidecar: Arc<PythonSidecar>) {
+    migration::watch(app_handle.clone());
+    tauri::async_runtime::spawn(async move {
+        match sidecar.wait_until_ready().await {
+            Ok(()) => {
+                startup_metrics::record(&app_handle, &sidecar);
+                let _ = app_handle.emit("sidecar-ready", ());
+                spawn_prewarm(PREWARM_DELAY);
+                spawn_warm_caches();
+                onboarding::seed_default_profile(&app_handle).await;
+                device::sync_on_ready(&app_handle).await;
+                power::on_sidecar_ready(&app_handle).await;
+                if let Err(e) = timezone::reconcile(&app_handle).await {
+                    println!("Timezone check skipped: {}", e);
+                }
+                today_stream::start(app_handle);
+            }
+            Err(e) => {
+                eprintln!("Python sidecar failed to become ready: {}", e);
+                let _ = app_handle.emit("sidecar-failed", e);
+            }
+        }
+    });
+}
+
 #[tauri::command]
 async fn get_today_state() -> Result<serde_json::Value, String> {
     python_bridge::call_api("/today_state").await
 }
 
+/// Forces the backend to rebuild today's state (after imports or settings changes) and returns it.
+#[tauri::command]
+async fn recompute_today_state() -> Result<serde_json::Value, String> {
+    python_bridge::post_api_untracked("/api/recompute", serde_json::json!({}), RECOMPUTE_TIMEOUT)
+        .await
+}
+
 #[tauri::command]
 async fn get_day_summary(date: String) -> Result<serde_json::Value, String> {
     python_bridge::call_api(&format!("/day_summary?date={}", date)).await
@@ -20,6 +167,7 @@ async fn get_day_summary(date: String) -> Result<serde_json::Value, String> {
 
 #[tauri::command]
 async fn generate_reflection(date: String, persona: String) -> Result<serde_json::Value, String> {
+    personas::validate_persona(&persona).await?;
     let body = serde_json::json!({
         "date": date,
         "persona": persona
@@ -27,6 +175,26 @@ async fn generate_reflection(date: String, persona: String) -> Result<serde_json
     python_bridge::post_api("/generate_reflection", body).await
 }
 
+#[tauri::command]
+async fn prewarm() -> Result<(), String> {
+    spawn_prewarm(Duration::ZERO);
+    Ok(())
+}
+
+/// Re-fetches cached read data, e.g. after a settings change made elsewhere.
+#[tauri::command]
+async fn warm_caches() -> Result<(), String> {
+    cache::invalidate(settings::SETTINGS_GET_ENDPOINT);
+    spawn_warm_caches();
+    Ok(())
+}
+
+/// Rebuilds the shared HTTP client, for when every call hangs on a dead connection pool.
+#[tauri::command]
+fn reset_http_client() {
+    python_bridge::reset_client("requested");
+}
+
 #[tauri::command]
 async fn get_passport_data() -> Result<serde_json::Value, String> {
     python_bridge::call_api("/api/passport").await
@@ -39,29 +207,72 @@ async fn get_profile() -> Result<serde_json::Value, String> {
 
 #[tauri::command]
 async fn update_settings(settings: serde_json::Value) -> Result<serde_json::Value, String> {
-    python_bridge::post_api("/api/update_settings", settings).await
+    let result = python_bridge::post_api("/api/update_settings", settings).await;
+    cache::invalidate(settings::SETTINGS_GET_ENDPOINT);
+    result
+}
+
+#[tauri::command]
+async fn get_idle_threshold() -> Result<u32, String> {
+    let res = python_bridge::call_api("/api/idle_threshold").await?;
+    res.get("seconds")
+        .and_then(|v| v.as_u64())
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| "Malformed idle threshold response".to_string())
+}
+
+#[tauri::command]
+async fn set_idle_threshold(seconds: u32) -> Result<u32, String> {
+    if !IDLE_THRESHOLD_RANGE.contains(&seconds) {
+        return Err(format!(
+            "Idle threshold must be between {} and {} seconds",
+            IDLE_THRESHOLD_RANGE.start(),
+            IDLE_THRESHOLD_RANGE.end()
+        ));
+    }
+    let body = serde_json::json!({ "seconds": seconds });
+    python_bridge::post_api("/api/idle_threshold", body).await?;
+    Ok(seconds)
 }
 
 #[tauri::command]
 async fn update_profile(name: String) -> Result<serde_json::Value, String> {
     let body = serde_json::json!({ "name": name });
-    python_bridge::post_api("/api/update_profile", body).await
+    let result = python_bridge::post_api("/api/update_profile", body).await;
+    cache::invalidate(settings::SETTINGS_GET_ENDPOINT);
+    result
 }
 
 #[tauri::command]
 async fn save_profile(profile: serde_json::Value) -> Result<serde_json::Value, String> {
-    python_bridge::post_api("/api/save_profile", profile).await
+    let result = python_bridge::post_api("/api/save_profile", profile).await;
+    cache::invalidate(settings::SETTINGS_GET_ENDPOINT);
+    result
 }
 
 #[tauri::command]
-async fn sync_device_id(device_id: String) -> Result<serde_json::Value, String> {
-    let body = serde_json::json!({ "deviceId": device_id });
-    python_bridge::post_api("/api/sync_device_id", body).await
+async fn reset_account() -> Result<serde_json::Value, String> {
+    python_bridge::post_api("/api/reset_account", serde_json::json!({})).await
 }
 
+/// Discards one day's tracked data (e.g. a misclassified screen-share) and returns the cleared state.
 #[tauri::command]
-async fn reset_account() -> Result<serde_json::Value, String> {
-    python_bridge::post_api("/api/reset_account", serde_json::json!({})).await
+async fn reset_day(
+    app_handle: tauri::AppHandle,
+    date: String,
+) -> Result<serde_json::Value, String> {
+    let day = reflections::parse_date(&date)?;
+    if day > chrono::Local::now().date_naive() {
+        return Err(format!("Cannot reset a future date: {}", date));
+    }
+
+    let res = python_bridge::post_api(
+        &format!("/api/reset_day?date={}", date),
+        serde_json::json!({}),
+    )
+    .await?;
+    let _ = app_handle.emit("day-reset", &date);
+    Ok(res)
 }
 
 #[tauri::command]
@@ -87,7 +298,20 @@ async fn save_reflection(text: String, persona: String) -> Result<serde_json::Va
         "text": text,
         "persona": persona
     });
-    python_bridge::post_api("/api/save_reflection", body).await
+    // The backend stamps reflections with the current local date
+    let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+    reflections::with_date_lock(&date, python_bridge::post_api("/api/save_reflection", body)).await
+}
+
+#[tauri::command]
+async fn set_read_only(enabled: bool) -> Result<bool, String> {
+    python_bridge::set_read_only(enabled);
+    Ok(enabled)
+}
+
+#[tauri::command]
+async fn is_read_only() -> Result<bool, String> {
+    Ok(python_bridge::is_read_only())
 }
 
 #[tauri::command]
@@ -95,18 +319,92 @@ async fn force_start_server(
     app_handle: tauri::AppHandle,
     sidecar: State<'_, Arc<PythonSidecar>>,
 ) -> Result<(), String> {
-    sidecar.start(&app_handle);
+    // A plain start always goes back to the real data dir
+    paths::set_data_dir_override(None);
+    sidecar.restart_async(&app_handle).await;
+    spawn_ready_watch(app_handle, sidecar.inner().clone());
+    Ok(())
+}
+
+/// Restarts the backend against a throwaway data dir (for QA / UI tests) and waits until it's up.
+#[tauri::command]
+async fn restart_sidecar_with_data_dir(
+    app_handle: tauri::AppHandle,
+    sidecar: State<'_, Arc<PythonSidecar>>,
+    path: String,
+) -> Result<(), String> {
+    // Validate first so a bad path never leaves us without a backend
+    let dir = std::path::PathBuf::from(path);
+    if !dir.is_dir() {
+        return Err(format!("Data dir does not exist: {}", dir.display()));
+    }
+    paths::check_writable(&dir)?;
+
+    paths::set_data_dir_override(Some(dir));
+    sidecar.restart_async(&app_handle).await;
+    sidecar.wait_until_ready().await?;
+    let _ = app_handle.emit("sidecar-ready", ());
+    Ok(())
+}
+
+/// Instant readiness check for the UI's first paint; `sidecar-ready` / `sidecar-failed` report changes.
+#[tauri::command]
+fn sidecar_is_ready(sidecar: State<'_, Arc<PythonSidecar>>) -> bool {
+    sidecar.is_ready()
+}
+
+#[tauri::command]
+async fn get_sidecar_errors(sidecar: State<'_, Arc<PythonSidecar>>) -> Result<Vec<String>, String> {
+    Ok(sidecar.recent_errors())
+}
+
+#[tauri::command]
+async fn clear_sidecar_errors(sidecar: State<'_, Arc<PythonSidecar>>) -> Result<(), String> {
+    sidecar.clear_errors();
+    Ok(())
+}
+
+#[tauri::command]
+async fn start_log_tail(
+    app_handle: tauri::AppHandle,
+    sidecar: State<'_, Arc<PythonSidecar>>,
+) -> Result<Option<std::path::PathBuf>, String> {
+    sidecar.log().start_tail(app_handle);
+    Ok(sidecar.log().path())
+}
+
+#[tauri::command]
+async fn stop_log_tail(sidecar: State<'_, Arc<PythonSidecar>>) -> Result<(), String> {
+    sidecar.log().stop_tail();
     Ok(())
 }
 
 #[tauri::command]
-async fn get_device_id() -> Result<serde_json::Value, String> {
-    python_bridge::call_api("/api/get_device_id").await
+async fn was_launched_minimized(launch: State<'_, LaunchArgs>) -> Result<bool, String> {
+    Ok(launch.minimized)
 }
 
+// Kept for older callers: the first page, in the original `{ history }` shape
 #[tauri::command]
 async fn get_reflection_history() -> Result<serde_json::Value, String> {
-    python_bridge::call_api("/api/reflection_history").await
+    let page = reflections::fetch_page(reflections::PageRequest::default()).await?;
+    Ok(serde_json::json!({ "history": page.items }))
+}
+
+// Only web links may leave the app; file:, javascript: and friends are refused
+#[tauri::command]
+async fn open_external(app_handle: tauri::AppHandle, url: String) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let parsed = reqwest::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        eprintln!("Refusing to open external URL: {}", url);
+        return Err(format!("Scheme not allowed: {}", parsed.scheme()));
+    }
+    app_handle
+        .opener()
+        .open_url(parsed.as_str(), None::<&str>)
+        .map_err(|e| e.to_string())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -114,6 +412,8 @@ pub fn run() {
     let sidecar = Arc::new(PythonSidecar::new());
     let sidecar_setup = sidecar.clone();
     let sidecar_exit = sidecar.clone();
+    let launch = LaunchArgs::parse(std::env::args());
+    let start_hidden = launch.minimized;
 
     tauri::Builder::default()
         .plugin(tauri_plugin_process::init())
@@ -124,89 +424,288 @@ pub fn run() {
             Some(vec!["--minimized"]),
         ))
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .manage(sidecar) // This manages Arc<PythonSidecar>
+        .manage(launch)
         .setup(move |app| {
-            sidecar_setup.start(&app.handle());
+            app.manage(LocalStore::load(app.handle())?);
+            http_trace::restore_http_trace(app.handle());
+            notifications::restore_snooze(app.handle());
+            main_window::restore_theme(app.handle());
+            main_window::restore_always_on_top(app.handle());
+            main_window::restore_window_state(app.handle());
+            main_window::restore_compact_mode(app.handle());
+            shortcuts::restore_shortcuts(app.handle());
+            backup::start_scheduler(app.handle().clone());
+            retention::start_scheduler(app.handle().clone());
+            power::start_monitor(app.handle().clone());
+            clock::start_watcher(app.handle().clone(), sidecar_setup.clone());
+            focus_alerts::start_watcher(app.handle().clone());
+            goals::start_watcher(app.handle().clone());
+            app_limits::start_watcher(app.handle().clone());
+            status_file::start_writer(app.handle().clone());
+            displays::start_watcher(app.handle().clone());
+            sync::start_watcher(app.handle().clone());
+            activity::start_stall_watcher(app.handle().clone());
+            reminders::schedule(app.handle().clone());
+            auto_pause::schedule(app.handle().clone());
+
+            // The window starts hidden; autostart launches stay in the tray,
+            // except on first run where onboarding has to be seen
+            if !start_hidden || !onboarding::marker_exists(app.handle()) {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+
+            runtime_files::reconcile_runtime_files(app.handle());
+            python_bridge::start_health_monitor(app.handle().clone(), sidecar_setup.clone());
+
+            // Don't spawn what can't possibly start; report what's missing instead
+            let blocking: Vec<String> =
+                diagnostics::run_environment_checks(app.handle(), &sidecar_setup)
+                    .into_iter()
+                    .filter(|check| check.blocks_startup())
+                    .map(|check| check.detail)
+                    .collect();
+            if blocking.is_empty() && lazy_start::enabled(app.handle()) {
+                lazy_start::spawn(app.handle().clone(), sidecar_setup.clone());
+            } else if blocking.is_empty() {
+                sidecar_setup.start(app.handle());
+                spawn_ready_watch(app.handle().clone(), sidecar_setup.clone());
+            } else {
+                let reason = blocking.join("; ");
+                eprintln!("Not starting sidecar: {}", reason);
+                sidecar_setup.fail_startup(reason.clone());
+                let _ = app.handle().emit("sidecar-failed", reason);
+            }
 
-            // Create tray menu
-            let show_item = MenuItem::with_id(app, "show", "Show Ovelo", true, None::<&str>)?;
-            let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_item, &quit_item])?;
+            // Create tray menu from the saved quick actions
+            let menu = tray::build_menu(app.handle())?;
 
             // Build system tray
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(tray::TRAY_ID)
                 .icon(app.default_window_icon().unwrap().clone())
                 .menu(&menu)
-                .menu_on_left_click(false)
+                .show_menu_on_left_click(false)
                 .tooltip("Ovelo - Focus Tracker")
-                .on_menu_event(|app, event| match event.id.as_ref() {
-                    "show" => {
-                        if let Some(window) = app.get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
-                    }
-                    "quit" => {
-                        app.exit(0);
-                    }
-                    _ => {}
-                })
-                .on_tray_icon_event(|tray, event| {
+                .on_menu_event(|app, event| tray::on_menu_event(app, event.id.as_ref()))
+                .on_tray_icon_event(|icon, event| {
                     if let TrayIconEvent::Click {
                         button: MouseButton::Left,
                         button_state: MouseButtonState::Up,
                         ..
                     } = event
                     {
-                        if let Some(window) = tray.app_handle().get_webview_window("main") {
-                            let _ = window.show();
-                            let _ = window.set_focus();
-                        }
+                        tray::on_left_click(icon.app_handle());
                     }
                 })
                 .build(app)?;
+            tray::restore_click_behavior(app.handle());
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             get_today_state,
+            recompute_today_state,
             get_day_summary,
             generate_reflection,
+            prewarm,
+            warm_caches,
+            reset_http_client,
             get_passport_data,
             get_profile,
             update_settings,
+            get_idle_threshold,
+            set_idle_threshold,
             update_profile,
             save_profile,
-            sync_device_id,
+            device::sync_device_id,
             reset_account,
+            reset_day,
             delete_account,
             logout,
             save_reflection,
+            set_read_only,
+            is_read_only,
             force_start_server,
-            get_device_id,
-            get_reflection_history
+            restart_sidecar_with_data_dir,
+            sidecar_is_ready,
+            get_sidecar_errors,
+            clear_sidecar_errors,
+            start_log_tail,
+            stop_log_tail,
+            device::get_device_id,
+            was_launched_minimized,
+            get_reflection_history,
+            open_external,
+            activity::get_category_breakdown,
+            activity::get_classifier_info,
+            activity::get_current_activity,
+            activity::get_last_sample_time,
+            activity::get_excluded_apps,
+            activity::set_excluded_apps,
+            activity::get_title_capture_mode,
+            activity::set_title_capture_mode,
+            activity::get_recent_events,
+            activity::pause_tracking,
+            activity::resume_tracking,
+            activity::get_records,
+            app_limits::get_app_limits,
+            app_limits::set_app_limit,
+            app_limits::remove_app_limit,
+            auto_pause::get_auto_pause_schedule,
+            auto_pause::set_auto_pause_schedule,
+            backup::create_backup,
+            backup::list_backups,
+            backup::restore_backup,
+            backup::set_backup_interval,
+            device::regenerate_device_id,
+            main_window::set_theme,
+            main_window::get_accent_color,
+            main_window::set_accent_color,
+            main_window::set_always_on_top,
+            main_window::set_compact_mode,
+            main_window::reset_window_state,
+            diagnostics::ping_endpoints,
+            diagnostics::check_ai_connectivity,
+            diagnostics::debug_call,
+            diagnostics::run_backend_self_test,
+            diagnostics::verify_data_integrity,
+            diagnostics::get_sidecar_resource_usage,
+            diagnostics::check_environment,
+            diagnostics::get_app_info,
+            diagnostics::get_sidecar_source,
+            diagnostics::create_diagnostic_bundle,
+            encryption::set_data_passphrase,
+            encryption::unlock_data,
+            encryption::is_data_locked,
+            interpreter::get_python_info,
+            displays::get_displays,
+            export::export_data,
+            export::export_stats_csv,
+            focus_alerts::configure_focus_alerts,
+            goals::get_daily_goal,
+            goals::set_daily_goal,
+            notifications::send_notification,
+            notifications::send_test_notification,
+            onboarding::is_first_run,
+            onboarding::complete_onboarding,
+            notifications::snooze_notifications,
+            notifications::clear_snooze,
+            notifications::get_notification_history,
+            notifications::clear_notification_history,
+            permissions::check_tracking_permissions,
+            permissions::request_tracking_permissions,
+            personas::get_personas,
+            power::set_power_mode,
+            profiles::list_profiles,
+            profiles::get_active_profile_id,
+            profiles::set_active_profile,
+            reflection_stream::generate_reflection_stream,
+            reflection_stream::cancel_reflection,
+            reclassify::reclassify_range,
+            reflections::export_reflection_markdown,
+            reflections::get_reflection_history_page,
+            reflections::delete_reflections_range,
+            reflections::get_reflection_config,
+            reflections::set_reflection_config,
+            reminders::set_reflection_reminder,
+            retention::get_retention_policy,
+            retention::set_retention_policy,
+            settings::get_settings,
+            settings::patch_settings,
+            settings::queue_settings_update,
+            shortcuts::list_shortcuts,
+            shortcuts::set_shortcut,
+            startup_metrics::get_startup_metrics,
+            status_file::set_status_file,
+            sync::get_sync_status,
+            sync::set_sync_enabled,
+            sync::sync_now,
+            sidecar_log::set_backend_log_level,
+            http_trace::set_http_trace,
+            lazy_start::ensure_sidecar_started,
+            lazy_start::set_lazy_start,
+            storage::get_storage_usage,
+            storage::compact_database,
+            storage::move_data_dir,
+            storage::secure_wipe,
+            timezone::get_backend_timezone,
+            timezone::set_backend_timezone,
+            tray::set_tray_click_behavior,
+            tray::set_tray_quick_actions,
+            updates::check_for_update,
+            webhook::set_webhook,
+            webhook::test_webhook
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(move |app_handle, event| {
             match event {
                 tauri::RunEvent::Exit => {
+                    python_bridge::wait_for_mutations(EXIT_DRAIN_TIMEOUT);
                     sidecar_exit.stop();
                 }
+                tauri::RunEvent::WindowEvent {
+                    label,
+                    event: tauri::WindowEvent::ThemeChanged(theme),
+                    ..
+                } if label == "main" => {
+                    main_window::on_theme_changed(app_handle, theme);
+                }
+                tauri::RunEvent::WindowEvent {
+                    label,
+                    event: tauri::WindowEvent::Focused(true),
+                    ..
+                } if label == "main" => {
+                    python_bridge::request_start();
+                }
+                tauri::RunEvent::WindowEvent {
+                    label,
+                    event: tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_),
+                    ..
+                } if label == "main" => {
+                    main_window::on_geometry_changed(app_handle);
+                }
                 tauri::RunEvent::WindowEvent {
                     label,
                     event: tauri::WindowEvent::CloseRequested { api, .. },
                     ..
-                } => {
+                } if label == "main" => {
                     // Minimize to tray instead of closing
-                    if label == "main" {
-                        api.prevent_close();
-                        if let Some(window) = app_handle.get_webview_window("main") {
-                            let _ = window.hide();
-                        }
+                    api.prevent_close();
+                    main_window::persist_window_state(app_handle);
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        let _ = window.hide();
                     }
                 }
                 _ => {}
             }
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> LaunchArgs {
+        LaunchArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn launch_args_detect_minimized() {
+        assert!(parse(&["ovelo", "--minimized"]).minimized);
+        assert!(parse(&["ovelo", "--autostart", "--minimized"]).minimized);
+        assert!(!parse(&["ovelo"]).minimized);
+    }
+
+    #[test]
+    fn launch_args_ignore_unknown_and_program_name() {
+        assert!(!parse(&["ovelo", "--verbose", "minimized", "--minimized=false"]).minimized);
+        // argv[0] is the program, never a flag
+        assert!(!parse(&["--minimized"]).minimized);
+        assert!(!parse(&[]).minimized);
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::paths;
+
+const STORE_FILE: &str = "desktop_state.json";
+
+/// Small key/value store for state owned by the desktop shell rather than the Python backend.
+/// Values live in memory and every change is flushed to disk atomically.
+pub struct LocalStore {
+    path: PathBuf,
+    values: Mutex<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl LocalStore {
+    pub fn load(app_handle: &tauri::AppHandle) -> Result<Self, String> {
+        let path = paths::app_data_dir(app_handle)?.join(STORE_FILE);
+        let values = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default();
+        Ok(Self {
+            path,
+            values: Mutex::new(values),
+        })
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let values = self.values.lock().unwrap();
+        values
+            .get(key)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    pub fn set<T: Serialize>(&self, key: &str, value: T) -> Result<(), String> {
+        let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+        let mut values = self.values.lock().unwrap();
+        values.insert(key.to_string(), value);
+        self.flush(&values)
+    }
+
+    pub fn remove(&self, key: &str) -> Result<(), String> {
+        let mut values = self.values.lock().unwrap();
+        if values.remove(key).is_some() {
+            self.flush(&values)?;
+        }
+        Ok(())
+    }
+
+    /// Forgets every value, as on a fresh install.
+    pub fn clear(&self) -> Result<(), String> {
+        let mut values = self.values.lock().unwrap();
+        values.clear();
+        self.flush(&values)
+    }
+
+    // Write-temp-then-rename so a crash never leaves a half-written file
+    fn flush(&self, values: &serde_json::Map<String, serde_json::Value>) -> Result<(), String> {
+        let raw = serde_json::to_string_pretty(values).map_err(|e| e.to_string())?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, raw).map_err(|e| e.to_string())?;
+        std::fs::rename(&tmp, &self.path).map_err(|e| e.to_string())
+    }
+}
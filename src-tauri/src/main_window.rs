@@ -0,0 +1,357 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, LogicalSize, Manager, PhysicalPosition, PhysicalSize};
+
+use crate::local_store::LocalStore;
+use crate::settings;
+
+const MAIN_WINDOW: &str = "main";
+
+const ACCENT_KEY: &str = "accent_color";
+const ALWAYS_ON_TOP_KEY: &str = "always_on_top";
+const COMPACT_KEY: &str = "compact_mode";
+// Logical size of the minimal timer window
+const COMPACT_SIZE: (f64, f64) = (320.0, 120.0);
+const THEME_KEY: &str = "theme";
+const WINDOW_STATE_KEY: &str = "window_state";
+// Moves and resizes arrive in bursts while dragging; only the settled geometry is written
+const WINDOW_STATE_SAVE_DELAY: Duration = Duration::from_millis(500);
+// Size from tauri.conf.json, used when the saved state is reset
+const DEFAULT_SIZE: (f64, f64) = (1200.0, 800.0);
+const THEMES: &[&str] = &["light", "dark", "system"];
+
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn native_theme(theme: &str) -> Option<tauri::Theme> {
+    match theme {
+        "light" => Some(tauri::Theme::Light),
+        "dark" => Some(tauri::Theme::Dark),
+        // None lets the OS decide
+        _ => None,
+    }
+}
+
+fn theme_name(theme: tauri::Theme) -> &'static str {
+    match theme {
+        tauri::Theme::Dark => "dark",
+        _ => "light",
+    }
+}
+
+fn main_window(app_handle: &tauri::AppHandle) -> Result<tauri::WebviewWindow, String> {
+    app_handle
+        .get_webview_window(MAIN_WINDOW)
+        .ok_or_else(|| "Main window not found".to_string())
+}
+
+fn apply_theme(app_handle: &tauri::AppHandle, theme: &str) -> Result<(), String> {
+    main_window(app_handle)?
+        .set_theme(native_theme(theme))
+        .map_err(|e| e.to_string())
+}
+
+/// Re-applies the saved theme to the native window chrome; called from the setup hook.
+pub fn restore_theme(app_handle: &tauri::AppHandle) {
+    if let Some(theme) = app_handle.state::<LocalStore>().get::<String>(THEME_KEY) {
+        if let Err(e) = apply_theme(app_handle, &theme) {
+            eprintln!("Failed to restore theme: {}", e);
+        }
+    }
+}
+
+/// Re-pins the main window if the user left it on top; called from the setup hook.
+pub fn restore_always_on_top(app_handle: &tauri::AppHandle) {
+    if app_handle
+        .state::<LocalStore>()
+        .get::<bool>(ALWAYS_ON_TOP_KEY)
+        == Some(true)
+    {
+        if let Err(e) = main_window(app_handle)
+            .and_then(|w| w.set_always_on_top(true).map_err(|e| e.to_string()))
+        {
+            eprintln!("Failed to restore always on top: {}", e);
+        }
+    }
+}
+
+/// Forwards OS theme changes to the frontend while the user follows the system theme.
+pub fn on_theme_changed(app_handle: &tauri::AppHandle, theme: tauri::Theme) {
+    let preference = app_handle.state::<LocalStore>().get::<String>(THEME_KEY);
+    if preference.as_deref().unwrap_or("system") == "system" {
+        let _ = app_handle.emit("theme-changed", theme_name(theme));
+    }
+}
+
+#[tauri::command]
+pub async fn set_theme(app_handle: tauri::AppHandle, theme: String) -> Result<String, String> {
+    if !THEMES.contains(&theme.as_str()) {
+        return Err(format!("Unknown theme: {}", theme));
+    }
+
+    apply_theme(&app_handle, &theme)?;
+    // Mirrored locally so startup can apply it before the backend is up
+    app_handle.state::<LocalStore>().set(THEME_KEY, &theme)?;
+    settings::apply_patch(serde_json::json!({ "theme": theme })).await?;
+
+    if theme == "system" {
+        if let Some(window) = app_handle.get_webview_window(MAIN_WINDOW) {
+            if let Ok(current) = window.theme() {
+                let _ = app_handle.emit("theme-changed", theme_name(current));
+            }
+        }
+    }
+    Ok(theme)
+}
+
+fn parse_accent(hex: &str) -> Result<String, String> {
+    let digits = hex
+        .strip_prefix('#')
+        .filter(|d| d.len() == 6 && d.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or_else(|| format!("Accent color must be #RRGGBB, got {:?}", hex))?;
+    Ok(format!("#{}", digits.to_ascii_lowercase()))
+}
+
+#[tauri::command]
+pub fn get_accent_color(app_handle: tauri::AppHandle) -> Option<String> {
+    app_handle.state::<LocalStore>().get(ACCENT_KEY)
+}
+
+/// Stores the accent locally and emits `accent-changed` so the frontend updates its CSS
+/// variables. Works while the backend is down or read-only.
+#[tauri::command]
+pub fn set_accent_color(app_handle: tauri::AppHandle, hex: String) -> Result<String, String> {
+    let accent = parse_accent(hex.trim())?;
+    app_handle.state::<LocalStore>().set(ACCENT_KEY, &accent)?;
+    let _ = app_handle.emit("accent-changed", &accent);
+    Ok(accent)
+}
+
+#[tauri::command]
+pub fn set_always_on_top(app_handle: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
+    let window = main_window(&app_handle)?;
+    window
+        .set_always_on_top(enabled)
+        .map_err(|e| e.to_string())?;
+    app_handle
+        .state::<LocalStore>()
+        .set(ALWAYS_ON_TOP_KEY, enabled)?;
+    window.is_always_on_top().map_err(|e| e.to_string())
+}
+
+/// Window geometry (physical pixels) to return to when leaving compact mode.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Geometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CompactState {
+    enabled: bool,
+    previous: Option<Geometry>,
+}
+
+// Moves `geometry` fully onto the monitor it overlaps (or the primary one), so a window
+// restored after a monitor was unplugged doesn't land off-screen
+fn clamp_to_monitors(window: &tauri::WebviewWindow, geometry: Geometry) -> Geometry {
+    let monitors = window.available_monitors().unwrap_or_default();
+    let overlaps = |m: &tauri::Monitor| {
+        let (pos, size) = (m.position(), m.size());
+        geometry.x < pos.x + size.width as i32
+            && geometry.x + geometry.width as i32 > pos.x
+            && geometry.y < pos.y + size.height as i32
+            && geometry.y + geometry.height as i32 > pos.y
+    };
+    let Some(monitor) = monitors
+        .iter()
+        .find(|m| overlaps(m))
+        .cloned()
+        .or_else(|| window.primary_monitor().ok().flatten())
+    else {
+        return geometry;
+    };
+
+    let (pos, size) = (monitor.position(), monitor.size());
+    let width = geometry.width.min(size.width);
+    let height = geometry.height.min(size.height);
+    Geometry {
+        x: geometry.x.clamp(pos.x, pos.x + (size.width - width) as i32),
+        y: geometry
+            .y
+            .clamp(pos.y, pos.y + (size.height - height) as i32),
+        width,
+        height,
+    }
+}
+
+fn apply_geometry(window: &tauri::WebviewWindow, geometry: Geometry) -> Result<(), String> {
+    let geometry = clamp_to_monitors(window, geometry);
+    window
+        .set_size(PhysicalSize::new(geometry.width, geometry.height))
+        .map_err(|e| e.to_string())?;
+    window
+        .set_position(PhysicalPosition::new(geometry.x, geometry.y))
+        .map_err(|e| e.to_string())
+}
+
+fn current_geometry(window: &tauri::WebviewWindow) -> Result<Geometry, String> {
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.inner_size().map_err(|e| e.to_string())?;
+    Ok(Geometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+fn enter_compact(window: &tauri::WebviewWindow) -> Result<(), String> {
+    let scale = window.scale_factor().map_err(|e| e.to_string())?;
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    window.set_decorations(false).map_err(|e| e.to_string())?;
+    apply_geometry(
+        window,
+        Geometry {
+            x: position.x,
+            y: position.y,
+            width: (COMPACT_SIZE.0 * scale).round() as u32,
+            height: (COMPACT_SIZE.1 * scale).round() as u32,
+        },
+    )
+}
+
+/// Re-enters compact mode if the app was closed in it; called from the setup hook.
+pub fn restore_compact_mode(app_handle: &tauri::AppHandle) {
+    let state = app_handle
+        .state::<LocalStore>()
+        .get::<CompactState>(COMPACT_KEY)
+        .unwrap_or_default();
+    if state.enabled {
+        if let Err(e) = main_window(app_handle).and_then(|w| enter_compact(&w)) {
+            eprintln!("Failed to restore compact mode: {}", e);
+        }
+    }
+}
+
+/// Shrinks the main window to a small undecorated timer, or restores the size and position it
+/// had before. The frontend switches layouts on `compact-mode-changed`.
+#[tauri::command]
+pub fn set_compact_mode(app_handle: tauri::AppHandle, enabled: bool) -> Result<bool, String> {
+    let window = main_window(&app_handle)?;
+    let store = app_handle.state::<LocalStore>();
+    let state = store.get::<CompactState>(COMPACT_KEY).unwrap_or_default();
+    if state.enabled == enabled {
+        return Ok(enabled);
+    }
+
+    let next = if enabled {
+        let previous = current_geometry(&window)?;
+        enter_compact(&window)?;
+        CompactState {
+            enabled: true,
+            previous: Some(previous),
+        }
+    } else {
+        window.set_decorations(true).map_err(|e| e.to_string())?;
+        if let Some(previous) = state.previous {
+            apply_geometry(&window, previous)?;
+        }
+        CompactState::default()
+    };
+    store.set(COMPACT_KEY, &next)?;
+    let _ = app_handle.emit("compact-mode-changed", enabled);
+    Ok(enabled)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct WindowState {
+    geometry: Geometry,
+    maximized: bool,
+}
+
+fn save_window_state(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let window = main_window(app_handle)?;
+    let store = app_handle.state::<LocalStore>();
+    // Compact mode and minimized windows report geometry that shouldn't come back on launch
+    let compact = store
+        .get::<CompactState>(COMPACT_KEY)
+        .is_some_and(|state| state.enabled);
+    if compact || window.is_minimized().unwrap_or(false) {
+        return Ok(());
+    }
+
+    let maximized = window.is_maximized().map_err(|e| e.to_string())?;
+    let previous = store.get::<WindowState>(WINDOW_STATE_KEY);
+    // While maximized keep the last normal geometry, so un-maximizing returns to it
+    let geometry = match (maximized, previous) {
+        (true, Some(previous)) => previous.geometry,
+        _ => current_geometry(&window)?,
+    };
+    store.set(
+        WINDOW_STATE_KEY,
+        WindowState {
+            geometry,
+            maximized,
+        },
+    )
+}
+
+/// Saves the main window's geometry once it stops moving; called on Moved/Resized.
+pub fn on_geometry_changed(app_handle: &tauri::AppHandle) {
+    let generation = SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(WINDOW_STATE_SAVE_DELAY).await;
+        if SAVE_GENERATION.load(Ordering::SeqCst) == generation {
+            if let Err(e) = save_window_state(&app_handle) {
+                eprintln!("Failed to save window state: {}", e);
+            }
+        }
+    });
+}
+
+/// Saves the geometry right away, for close-to-tray where the window is about to be hidden.
+pub fn persist_window_state(app_handle: &tauri::AppHandle) {
+    SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    if let Err(e) = save_window_state(app_handle) {
+        eprintln!("Failed to save window state: {}", e);
+    }
+}
+
+/// Puts the main window back where it was last time; called from the setup hook before
+/// the window is shown. Positions are clamped to the monitors connected now.
+pub fn restore_window_state(app_handle: &tauri::AppHandle) {
+    let Some(state) = app_handle
+        .state::<LocalStore>()
+        .get::<WindowState>(WINDOW_STATE_KEY)
+    else {
+        return;
+    };
+    let restored = main_window(app_handle).and_then(|window| {
+        apply_geometry(&window, state.geometry)?;
+        if state.maximized {
+            window.maximize().map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    });
+    if let Err(e) = restored {
+        eprintln!("Failed to restore window state: {}", e);
+    }
+}
+
+/// Forgets the saved geometry and returns the window to its default size, centered.
+#[tauri::command]
+pub fn reset_window_state(app_handle: tauri::AppHandle) -> Result<(), String> {
+    SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    app_handle.state::<LocalStore>().remove(WINDOW_STATE_KEY)?;
+    let window = main_window(&app_handle)?;
+    window.unmaximize().map_err(|e| e.to_string())?;
+    window
+        .set_size(LogicalSize::new(DEFAULT_SIZE.0, DEFAULT_SIZE.1))
+        .map_err(|e| e.to_string())?;
+    window.center().map_err(|e| e.to_string())
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::python_bridge;
+
+const STATUS_ENDPOINT: &str = "/api/migration_status";
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const POLL_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrationProgress {
+    step: u64,
+    total: u64,
+}
+
+// (step, total) while a migration runs, None once the backend reports it's done
+fn progress(status: &serde_json::Value) -> Option<MigrationProgress> {
+    if !status
+        .get("migrating")
+        .and_then(|m| m.as_bool())
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let field = |key: &str| status.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    Some(MigrationProgress {
+        step: field("step"),
+        total: field("total"),
+    })
+}
+
+/// Relays backend schema-migration progress as `migration-progress` / `migration-complete`
+/// events for as long as the sidecar is starting. Launches without a migration emit nothing.
+pub fn watch(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut migrating = false;
+        loop {
+            // Connection errors just mean the server isn't listening yet
+            let status = python_bridge::get_unqueued(STATUS_ENDPOINT, POLL_TIMEOUT).await;
+            match status.as_ref().ok().map(progress) {
+                Some(Some(progress)) => {
+                    migrating = true;
+                    let _ = app_handle.emit("migration-progress", progress);
+                }
+                Some(None) if migrating => {
+                    let _ = app_handle.emit("migration-complete", ());
+                    return;
+                }
+                _ => {}
+            }
+            // Startup finished (or failed) and there's no migration left to follow
+            if !python_bridge::is_starting() && (!migrating || status.is_err()) {
+                return;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
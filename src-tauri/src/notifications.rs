@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::local_store::LocalStore;
+
+const SNOOZE_KEY: &str = "notifications_snoozed_until";
+const HISTORY_KEY: &str = "notification_history";
+const HISTORY_LIMIT: usize = 100;
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRecord {
+    ts: DateTime<Utc>,
+    title: String,
+    body: String,
+}
+
+// Kept in the LocalStore, newest last, so the list survives a relaunch
+fn record(app_handle: &tauri::AppHandle, title: &str, body: &str) {
+    let store = app_handle.state::<LocalStore>();
+    let mut history = store
+        .get::<Vec<NotificationRecord>>(HISTORY_KEY)
+        .unwrap_or_default();
+    history.push(NotificationRecord {
+        ts: Utc::now(),
+        title: title.to_string(),
+        body: body.to_string(),
+    });
+    let excess = history.len().saturating_sub(HISTORY_LIMIT);
+    history.drain(..excess);
+    if let Err(e) = store.set(HISTORY_KEY, &history) {
+        eprintln!("Failed to save notification history: {}", e);
+    }
+}
+
+fn snoozed_until(app_handle: &tauri::AppHandle) -> Option<DateTime<Utc>> {
+    app_handle
+        .state::<LocalStore>()
+        .get::<DateTime<Utc>>(SNOOZE_KEY)
+        .filter(|until| *until > Utc::now())
+}
+
+/// Shows a native notification unless the user has snoozed them, and adds it to the history
+/// either way so snoozed ones can still be read later.
+/// All app-originated notifications should go through here.
+pub fn notify(app_handle: &tauri::AppHandle, title: &str, body: &str) {
+    record(app_handle, title, body);
+    if snoozed_until(app_handle).is_some() {
+        println!("Notification suppressed (snoozed): {}", title);
+        return;
+    }
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(title)
+        .body(body)
+        .show()
+    {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
+
+// Clears the snooze once it expires, unless it was changed in the meantime
+fn schedule_snooze_end(app_handle: tauri::AppHandle, until: DateTime<Utc>) {
+    tauri::async_runtime::spawn(async move {
+        let remaining = (until - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(remaining).await;
+
+        let store = app_handle.state::<LocalStore>();
+        if store.get::<DateTime<Utc>>(SNOOZE_KEY) == Some(until) {
+            let _ = store.remove(SNOOZE_KEY);
+            let _ = app_handle.emit("snooze-ended", ());
+        }
+    });
+}
+
+/// Re-arms a snooze persisted by a previous run, dropping it if it already lapsed.
+pub fn restore_snooze(app_handle: &tauri::AppHandle) {
+    let store = app_handle.state::<LocalStore>();
+    match store.get::<DateTime<Utc>>(SNOOZE_KEY) {
+        Some(until) if until > Utc::now() => schedule_snooze_end(app_handle.clone(), until),
+        Some(_) => {
+            let _ = store.remove(SNOOZE_KEY);
+        }
+        None => {}
+    }
+}
+
+/// Lets the frontend route its notifications through the same snooze gate.
+#[tauri::command]
+pub async fn send_notification(
+    app_handle: tauri::AppHandle,
+    title: String,
+    body: String,
+) -> Result<(), String> {
+    notify(&app_handle, &title, &body);
+    Ok(())
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationTest {
+    delivered: bool,
+    /// "granted" / "denied" / "prompt" where the platform reports it, None where it can't tell
+    permission: Option<String>,
+    /// Where the user can grant notification access, when we know
+    settings_url: Option<&'static str>,
+    detail: Option<String>,
+}
+
+/// Fires a known notification, ignoring any snooze, so the user can confirm alerts get through.
+#[tauri::command]
+pub fn send_test_notification(app_handle: tauri::AppHandle) -> NotificationTest {
+    // The desktop plugin always answers "granted", so only trust the state on mobile
+    let permission = if cfg!(any(target_os = "android", target_os = "ios")) {
+        app_handle
+            .notification()
+            .permission_state()
+            .ok()
+            .map(|state| state.to_string())
+    } else {
+        None
+    };
+    let settings_url = if cfg!(target_os = "macos") {
+        // macOS drops unauthorized notifications without an error
+        Some("x-apple.systempreferences:com.apple.preference.notifications")
+    } else {
+        None
+    };
+
+    let shown = app_handle
+        .notification()
+        .builder()
+        .title("Ovelo")
+        .body("Notifications are working")
+        .show();
+    NotificationTest {
+        delivered: shown.is_ok() && permission.as_deref().is_none_or(|p| p == "granted"),
+        permission,
+        settings_url,
+        detail: shown.err().map(|e| e.to_string()),
+    }
+}
+
+/// Suppresses notifications until the given RFC 3339 timestamp.
+#[tauri::command]
+pub async fn snooze_notifications(
+    app_handle: tauri::AppHandle,
+    until: String,
+) -> Result<String, String> {
+    let until = DateTime::parse_from_rfc3339(&until)
+        .map_err(|e| format!("Invalid snooze timestamp: {}", e))?
+        .with_timezone(&Utc);
+    if until <= Utc::now() {
+        return Err("Snooze end must be in the future".to_string());
+    }
+
+    snooze(&app_handle, until)?;
+    Ok(until.to_rfc3339())
+}
+
+/// Suppresses notifications until `until`, surviving a relaunch.
+pub fn snooze(app_handle: &tauri::AppHandle, until: DateTime<Utc>) -> Result<(), String> {
+    app_handle.state::<LocalStore>().set(SNOOZE_KEY, until)?;
+    schedule_snooze_end(app_handle.clone(), until);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_snooze(app_handle: tauri::AppHandle) -> Result<(), String> {
+    app_handle.state::<LocalStore>().remove(SNOOZE_KEY)?;
+    let _ = app_handle.emit("snooze-ended", ());
+    Ok(())
+}
+
+/// Notifications the app has sent, newest first.
+#[tauri::command]
+pub fn get_notification_history(app_handle: tauri::AppHandle) -> Vec<NotificationRecord> {
+    let mut history = app_handle
+        .state::<LocalStore>()
+        .get::<Vec<NotificationRecord>>(HISTORY_KEY)
+        .unwrap_or_default();
+    history.reverse();
+    history
+}
+
+#[tauri::command]
+pub fn clear_notification_history(app_handle: tauri::AppHandle) -> Result<(), String> {
+    app_handle.state::<LocalStore>().remove(HISTORY_KEY)
+}
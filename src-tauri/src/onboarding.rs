@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use tauri::Manager;
+
+use crate::local_store::LocalStore;
+use crate::paths;
+use crate::python_bridge;
+use crate::settings::Settings;
+
+const MARKER_FILE: &str = "onboarding_complete";
+// Set once we've seeded the backend with a default profile on a fresh install
+const SEEDED_KEY: &str = "default_profile_seeded";
+
+fn marker_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::app_data_dir(app_handle)?.join(MARKER_FILE))
+}
+
+pub fn marker_exists(app_handle: &tauri::AppHandle) -> bool {
+    marker_path(app_handle).is_ok_and(|path| path.exists())
+}
+
+// Written to a temp file and renamed into place, so the marker is either fully there or absent
+fn write_marker(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let path = marker_path(app_handle)?;
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, chrono::Utc::now().to_rfc3339()).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+async fn backend_has_profile() -> Result<bool, String> {
+    let res = python_bridge::call_api("/api/check_profile").await?;
+    Ok(res.get("exists").and_then(|v| v.as_bool()).unwrap_or(false))
+}
+
+/// Seeds a default profile on a fresh install; run once the sidecar is ready.
+pub async fn seed_default_profile(app_handle: &tauri::AppHandle) {
+    if marker_exists(app_handle) {
+        return;
+    }
+    match backend_has_profile().await {
+        Ok(false) => {
+            let store = app_handle.state::<LocalStore>();
+            let _ = store.set(SEEDED_KEY, true);
+            let profile = serde_json::to_value(Settings::default()).unwrap_or_default();
+            if let Err(e) = python_bridge::post_api("/api/save_profile", profile).await {
+                eprintln!("Failed to seed default profile: {}", e);
+            }
+        }
+        Ok(true) => {}
+        Err(e) => eprintln!("Could not check for an existing profile: {}", e),
+    }
+}
+
+#[tauri::command]
+pub async fn is_first_run(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    if marker_exists(&app_handle) {
+        return Ok(false);
+    }
+    // Installs from before the marker existed already have a real profile; adopt them
+    let seeded = app_handle
+        .state::<LocalStore>()
+        .get::<bool>(SEEDED_KEY)
+        .unwrap_or(false);
+    if !seeded && backend_has_profile().await.unwrap_or(false) {
+        write_marker(&app_handle)?;
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn complete_onboarding(app_handle: tauri::AppHandle) -> Result<(), String> {
+    write_marker(&app_handle)
+}
@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::Manager;
+
+use crate::local_store::LocalStore;
+
+// App-owned data directory (created on first use)
+pub fn app_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+const PID_FILE: &str = "sidecar.pid";
+
+pub fn pid_file(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app_data_dir(app_handle)?.join(PID_FILE))
+}
+
+// Runtime data dir for the sidecar (QA fixture runs); passed to it as OVELO_DATA_DIR
+static DATA_DIR_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+pub fn set_data_dir_override(dir: Option<PathBuf>) {
+    *DATA_DIR_OVERRIDE.lock().unwrap() = dir;
+}
+
+pub fn data_dir_override() -> Option<PathBuf> {
+    DATA_DIR_OVERRIDE.lock().unwrap().clone()
+}
+
+// Set by `move_data_dir` when the user relocates their data
+pub const DATA_DIR_KEY: &str = "data_dir";
+
+/// Data dir the sidecar must be pointed at explicitly (runtime override, then the
+/// relocated dir), or None to let it use its own default.
+pub fn sidecar_data_dir(app_handle: &tauri::AppHandle) -> Option<PathBuf> {
+    data_dir_override().or_else(|| {
+        app_handle
+            .try_state::<LocalStore>()?
+            .get::<PathBuf>(DATA_DIR_KEY)
+    })
+}
+
+/// Where the bundled backend keeps its data and logs (Config.BASE_DIR on the Python side),
+/// unless overridden at runtime, relocated by the user, or set with `OVELO_DATA_DIR`.
+pub fn backend_data_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    if let Some(dir) = sidecar_data_dir(app_handle) {
+        return Ok(dir);
+    }
+    if let Some(dir) = std::env::var_os("OVELO_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    let path = app_handle.path();
+    let dir = if cfg!(any(target_os = "windows", target_os = "macos")) {
+        path.data_dir().map(|dir| dir.join("Ovelo"))
+    } else {
+        path.home_dir().map(|dir| dir.join(".ovelo"))
+    };
+    dir.map_err(|e| e.to_string())
+}
+
+/// Checks that files can be created in `dir` by writing and removing a probe file.
+pub fn check_writable(dir: &Path) -> Result<(), String> {
+    let probe = dir.join(".ovelo_write_probe");
+    std::fs::write(&probe, b"ok")
+        .map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+pub fn log_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app_data_dir(app_handle)?.join("logs"))
+}
@@ -0,0 +1,93 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrackingPermissions {
+    accessibility: bool,
+    screen_recording: bool,
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+        fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    pub const ACCESSIBILITY_PANE: &str =
+        "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility";
+    pub const SCREEN_RECORDING_PANE: &str =
+        "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture";
+
+    pub fn accessibility() -> bool {
+        unsafe { AXIsProcessTrusted() }
+    }
+
+    pub fn screen_recording() -> bool {
+        unsafe { CGPreflightScreenCaptureAccess() }
+    }
+
+    /// Shows the system prompt; macOS only does so once, after that the pane has to be used.
+    pub fn request_screen_recording() -> bool {
+        unsafe { CGRequestScreenCaptureAccess() }
+    }
+}
+
+fn current() -> TrackingPermissions {
+    #[cfg(target_os = "macos")]
+    {
+        TrackingPermissions {
+            accessibility: macos::accessibility(),
+            screen_recording: macos::screen_recording(),
+        }
+    }
+    // Other platforms don't gate window/input tracking behind a permission
+    #[cfg(not(target_os = "macos"))]
+    {
+        TrackingPermissions {
+            accessibility: true,
+            screen_recording: true,
+        }
+    }
+}
+
+/// What the tracker is allowed to see. Without these on macOS the backend records nothing.
+#[tauri::command]
+pub fn check_tracking_permissions() -> TrackingPermissions {
+    current()
+}
+
+/// Prompts for whatever is missing and opens the matching System Settings pane.
+/// Returns the state afterwards; grants made in System Settings show up on the next check.
+#[tauri::command]
+pub fn request_tracking_permissions(
+    app_handle: tauri::AppHandle,
+) -> Result<TrackingPermissions, String> {
+    #[cfg(target_os = "macos")]
+    {
+        use tauri_plugin_opener::OpenerExt;
+
+        let state = current();
+        if !state.screen_recording && !macos::request_screen_recording() {
+            app_handle
+                .opener()
+                .open_url(macos::SCREEN_RECORDING_PANE, None::<&str>)
+                .map_err(|e| e.to_string())?;
+        }
+        if !state.accessibility {
+            app_handle
+                .opener()
+                .open_url(macos::ACCESSIBILITY_PANE, None::<&str>)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    #[cfg(not(target_os = "macos"))]
+    let _ = app_handle;
+    Ok(current())
+}
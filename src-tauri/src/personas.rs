@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache;
+
+const PERSONAS_ENDPOINT: &str = "/api/personas";
+const PERSONAS_TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Persona {
+    pub id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+pub async fn fetch_personas() -> Result<Vec<Persona>, String> {
+    let res = cache::call_api_cached(PERSONAS_ENDPOINT, PERSONAS_TTL).await?;
+    // Accept either a bare list or `{ "personas": [...] }`
+    let list = res.get("personas").cloned().unwrap_or(res);
+    serde_json::from_value(list).map_err(|e| format!("Malformed personas response: {}", e))
+}
+
+/// Rejects persona ids the backend doesn't know about. If the list can't be fetched
+/// (e.g. an older backend without the endpoint) the persona is let through unchecked.
+pub async fn validate_persona(persona: &str) -> Result<(), String> {
+    match fetch_personas().await {
+        Ok(personas) if !personas.iter().any(|p| p.id == persona) => {
+            Err(format!("Unknown reflection persona: {}", persona))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub async fn get_personas() -> Result<Vec<Persona>, String> {
+    fetch_personas().await
+}
@@ -0,0 +1,211 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::local_store::LocalStore;
+use crate::python_bridge::{self, PythonSidecar};
+
+const POWER_MODES: &[&str] = &["performance", "balanced", "battery-saver"];
+// Passing "auto" drops the explicit choice and goes back to following the power source
+const AUTO_MODE: &str = "auto";
+const MODE_KEY: &str = "power_mode";
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+// Power source whose automatic mode the backend last accepted; reset when the sidecar restarts
+static APPLIED: Mutex<Option<bool>> = Mutex::new(None);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PowerModeChanged {
+    mode: String,
+    automatic: bool,
+}
+
+/// Whether the machine is running on battery, or None if it can't tell (e.g. desktops).
+#[cfg(target_os = "windows")]
+fn on_battery() -> Option<bool> {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    unsafe { GetSystemPowerStatus(&mut status) }.ok()?;
+    match status.ACLineStatus {
+        0 => Some(true),
+        1 => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn on_battery() -> Option<bool> {
+    let mut saw_mains = false;
+    for entry in std::fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+        let path = entry.path();
+        let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+        if kind.trim() != "Mains" {
+            continue;
+        }
+        saw_mains = true;
+        if std::fs::read_to_string(path.join("online")).is_ok_and(|online| online.trim() == "1") {
+            return Some(false);
+        }
+    }
+    saw_mains.then_some(true)
+}
+
+#[cfg(target_os = "macos")]
+fn on_battery() -> Option<bool> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    if text.contains("'AC Power'") {
+        Some(false)
+    } else if text.contains("'Battery Power'") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn on_battery() -> Option<bool> {
+    None
+}
+
+fn automatic_mode(on_battery: bool) -> &'static str {
+    if on_battery {
+        "battery-saver"
+    } else {
+        "balanced"
+    }
+}
+
+async fn apply_mode(
+    app_handle: &tauri::AppHandle,
+    mode: &str,
+    automatic: bool,
+) -> Result<(), String> {
+    python_bridge::post_api("/api/power_mode", serde_json::json!({ "mode": mode })).await?;
+    let _ = app_handle.emit(
+        "power-mode-changed",
+        PowerModeChanged {
+            mode: mode.to_string(),
+            automatic,
+        },
+    );
+    Ok(())
+}
+
+// Applies the mode for the current power source unless the backend already has it
+async fn follow_power_source(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let Some(battery) = tokio::task::spawn_blocking(on_battery).await.ok().flatten() else {
+        return Ok(());
+    };
+    if *APPLIED.lock().unwrap() == Some(battery) {
+        return Ok(());
+    }
+    apply_mode(app_handle, automatic_mode(battery), true).await?;
+    // Only remember the transition once the backend accepted it, so it's retried
+    *APPLIED.lock().unwrap() = Some(battery);
+    Ok(())
+}
+
+/// Follows AC/battery transitions unless the user picked a mode explicitly.
+pub fn start_monitor(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let explicit = app_handle.state::<LocalStore>().get::<String>(MODE_KEY);
+            // A deferred sidecar gets the mode once it's started for real
+            if explicit.is_none() && !python_bridge::is_deferred() {
+                let _ = follow_power_source(&app_handle).await;
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+/// Sends the stored mode to a sidecar that just became ready, since a fresh backend starts
+/// on its default sampling.
+pub async fn on_sidecar_ready(app_handle: &tauri::AppHandle) {
+    *APPLIED.lock().unwrap() = None;
+    let explicit = app_handle.state::<LocalStore>().get::<String>(MODE_KEY);
+    let result = match explicit {
+        Some(mode) => apply_mode(app_handle, &mode, false).await,
+        None => follow_power_source(app_handle).await,
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to apply power mode: {}", e);
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SystemResumed {
+    slept_seconds: u64,
+}
+
+fn rfc3339(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339()
+}
+
+/// Tells the backend where a sleep started and ended; called by the clock watcher.
+pub async fn on_wake(
+    app_handle: &tauri::AppHandle,
+    sidecar: &PythonSidecar,
+    slept_at: SystemTime,
+    woke_at: SystemTime,
+) {
+    let slept_seconds = woke_at
+        .duration_since(slept_at)
+        .unwrap_or_default()
+        .as_secs();
+    println!("System woke after ~{} s asleep", slept_seconds);
+
+    // The backend's sockets may not have survived the sleep
+    if sidecar.running_since().is_some() && !python_bridge::is_starting() {
+        if let Err(e) = sidecar.wait_until_ready().await {
+            eprintln!("Sidecar not ready after wake: {}", e);
+            let _ = app_handle.emit("sidecar-failed", e);
+            return;
+        }
+    }
+
+    // Close the interval at the last moment we saw the machine awake, so the sleep isn't
+    // recorded as one long idle or focus block. A sidecar not yet started recorded nothing.
+    if python_bridge::is_deferred() {
+        let _ = app_handle.emit("system-resumed", SystemResumed { slept_seconds });
+        return;
+    }
+    let suspend = serde_json::json!({ "at": rfc3339(slept_at) });
+    if let Err(e) = python_bridge::post_api("/api/suspend", suspend).await {
+        eprintln!("Failed to notify backend of sleep: {}", e);
+    }
+    let resume = serde_json::json!({ "at": rfc3339(woke_at) });
+    if let Err(e) = python_bridge::post_api("/api/resume", resume).await {
+        eprintln!("Failed to notify backend of wake: {}", e);
+    }
+    let _ = app_handle.emit("system-resumed", SystemResumed { slept_seconds });
+}
+
+#[tauri::command]
+pub async fn set_power_mode(app_handle: tauri::AppHandle, mode: String) -> Result<String, String> {
+    let store = app_handle.state::<LocalStore>();
+    if mode == AUTO_MODE {
+        store.remove(MODE_KEY)?;
+        let battery = tokio::task::spawn_blocking(on_battery).await.ok().flatten();
+        let detected = automatic_mode(battery.unwrap_or(false));
+        apply_mode(&app_handle, detected, true).await?;
+        *APPLIED.lock().unwrap() = battery;
+        return Ok(detected.to_string());
+    }
+
+    if !POWER_MODES.contains(&mode.as_str()) {
+        return Err(format!("Unknown power mode: {}", mode));
+    }
+    store.set(MODE_KEY, &mode)?;
+    apply_mode(&app_handle, &mode, false).await?;
+    Ok(mode)
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::cache;
+use crate::python_bridge;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileSummary {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+async fn fetch_profiles() -> Result<Vec<ProfileSummary>, String> {
+    let res = python_bridge::call_api("/api/profiles").await?;
+    // Accept either a bare list or `{ "profiles": [...] }`
+    let list = res.get("profiles").cloned().unwrap_or(res);
+    serde_json::from_value(list).map_err(|e| format!("Malformed profiles response: {}", e))
+}
+
+#[tauri::command]
+pub async fn list_profiles() -> Result<Vec<ProfileSummary>, String> {
+    fetch_profiles().await
+}
+
+#[tauri::command]
+pub async fn get_active_profile_id() -> Result<String, String> {
+    let res = python_bridge::call_api("/api/active_profile").await?;
+    res.get("id")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .ok_or_else(|| "Malformed active profile response".to_string())
+}
+
+/// Switches the backend to another profile. Everything cached for the old profile is
+/// dropped and `profile-switched` tells every screen to refetch.
+#[tauri::command]
+pub async fn set_active_profile(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    let profiles = fetch_profiles().await?;
+    if !profiles.iter().any(|p| p.id == id) {
+        return Err(format!("Unknown profile: {}", id));
+    }
+
+    python_bridge::post_api("/api/active_profile", serde_json::json!({ "id": id })).await?;
+    cache::clear();
+    let _ = app_handle.emit("profile-switched", &id);
+    Ok(())
+}
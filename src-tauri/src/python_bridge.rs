@@ -1,11 +1,24 @@
-use std::path::PathBuf;
-use std::process::{Child, Command};
-use std::sync::Mutex;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStderr, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use tokio::sync::{watch, Notify};
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+
+use crate::http_trace;
+use crate::integrity;
+use crate::interpreter;
+use crate::paths;
+use crate::sidecar_log::{self, SidecarLog};
 
 #[cfg(target_os = "windows")]
 use windows::Win32::System::JobObjects::{
@@ -18,8 +31,417 @@ use windows::Win32::System::JobObjects::{
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+// Config.PORT on the Python side
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 5006;
+// Loopback addresses tried for a local sidecar. Flask binds IPv4 by default, but on some
+// dual-stack machines the server ends up reachable on IPv6 only (or the reverse)
+const LOOPBACK_HOSTS: &[&str] = &["127.0.0.1", "::1"];
+
+// Readiness polling: capped exponential backoff plus jitter, with a hard attempt ceiling
+const READY_ENDPOINT: &str = "/health";
+const READY_MAX_ATTEMPTS: u32 = 30;
+const READY_BASE_DELAY_MS: u64 = 100;
+const READY_MAX_DELAY_MS: u64 = 2000;
+const READY_JITTER_MS: std::ops::RangeInclusive<u64> = 50..=150;
+const READY_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+// A schema migration on first launch after an update may legitimately take a while
+const MIGRATION_MAX_WAIT: Duration = Duration::from_secs(600);
+// Liveness checks once ready: a local child that exited is caught on the next check, a hung
+// or unreachable server after HEALTH_MAX_FAILURES probes in a row
+const HEALTH_INTERVAL: Duration = Duration::from_secs(5);
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const HEALTH_MAX_FAILURES: u32 = 3;
+
+// Shared HTTP client tuning. This only ever talks to localhost, so proxies are disabled
+// outright: system proxy detection is slow and can route local traffic through a corporate proxy.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+const TCP_KEEPALIVE: Duration = Duration::from_secs(30);
+
+// Consecutive request timeouts after which the pool is assumed wedged (e.g. every pooled
+// connection left half-closed by a network flap) and the client is rebuilt
+const TIMEOUTS_BEFORE_RESET: u32 = 3;
+
+static HTTP_CLIENT: Mutex<Option<reqwest::Client>> = Mutex::new(None);
+static CONSECUTIVE_TIMEOUTS: AtomicU32 = AtomicU32::new(0);
+
+fn build_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .no_proxy()
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .build()
+        .expect("failed to build HTTP client")
+}
+
+// Cheap to clone; all clones share one connection pool
+fn client() -> reqwest::Client {
+    HTTP_CLIENT
+        .lock()
+        .unwrap()
+        .get_or_insert_with(build_client)
+        .clone()
+}
+
+/// Drops the shared client so the next request builds a fresh one with an empty pool.
+/// Requests already holding a clone finish on the old pool.
+pub fn reset_client(reason: &str) {
+    HTTP_CLIENT.lock().unwrap().take();
+    CONSECUTIVE_TIMEOUTS.store(0, Ordering::Relaxed);
+    if cfg!(debug_assertions) || sidecar_log::debug_enabled() {
+        println!("[debug] http-client-reset: {}", reason);
+    }
+}
+
+fn record_outcome(result: &Result<reqwest::Response, reqwest::Error>) {
+    match result {
+        Err(e) if e.is_timeout() => {
+            let timeouts = CONSECUTIVE_TIMEOUTS.fetch_add(1, Ordering::Relaxed) + 1;
+            if timeouts >= TIMEOUTS_BEFORE_RESET {
+                reset_client(&format!("{} consecutive timeouts", timeouts));
+            }
+        }
+        // Any answer at all shows the pool works; other errors say nothing either way
+        Ok(_) => CONSECUTIVE_TIMEOUTS.store(0, Ordering::Relaxed),
+        Err(_) => {}
+    }
+}
+
+/// Host and port the Python server is reached on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiEndpoint {
+    pub host: String,
+    pub port: u16,
+}
+
+impl ApiEndpoint {
+    // `OVELO_HOST` is only honored in debug builds; release builds always talk to localhost
+    fn from_env() -> Self {
+        let host = std::env::var("OVELO_HOST")
+            .ok()
+            .filter(|host| cfg!(debug_assertions) && !host.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_HOST.to_string());
+        Self {
+            host: host.trim().to_string(),
+            port: DEFAULT_PORT,
+        }
+    }
+
+    /// A remote endpoint means someone else runs the server, so we don't spawn one.
+    pub fn is_remote(&self) -> bool {
+        !matches!(
+            self.host.as_str(),
+            "127.0.0.1" | "localhost" | "::1" | "[::1]"
+        )
+    }
+
+    fn with_host(&self, host: &str) -> Self {
+        Self {
+            host: host.to_string(),
+            port: self.port,
+        }
+    }
+
+    // Addresses the server may be listening on, configured host first
+    fn candidates(&self) -> Vec<ApiEndpoint> {
+        if self.is_remote() {
+            return vec![self.clone()];
+        }
+        let mut candidates = vec![self.clone()];
+        candidates.extend(
+            LOOPBACK_HOSTS
+                .iter()
+                .filter(|host| **host != self.host)
+                .map(|host| self.with_host(host)),
+        );
+        candidates
+    }
+
+    pub fn url(&self, endpoint: &str) -> String {
+        // Bare IPv6 literals need brackets inside a URL
+        if self.host.contains(':') && !self.host.starts_with('[') {
+            format!("http://[{}]:{}{}", self.host, self.port, endpoint)
+        } else {
+            format!("http://{}:{}{}", self.host, self.port, endpoint)
+        }
+    }
+}
+
+static API_ENDPOINT: OnceLock<ApiEndpoint> = OnceLock::new();
+
+pub fn api_endpoint() -> &'static ApiEndpoint {
+    API_ENDPOINT.get_or_init(ApiEndpoint::from_env)
+}
+
+// Loopback host the readiness check last reached the server on
+static RESOLVED_HOST: Mutex<Option<String>> = Mutex::new(None);
+
+/// Where requests actually go: the address the readiness check found answering, or the
+/// configured one until it has run.
+pub fn active_endpoint() -> ApiEndpoint {
+    match RESOLVED_HOST.lock().unwrap().as_deref() {
+        Some(host) => api_endpoint().with_host(host),
+        None => api_endpoint().clone(),
+    }
+}
+
+fn remember_host(endpoint: &ApiEndpoint) {
+    let mut resolved = RESOLVED_HOST.lock().unwrap();
+    if resolved.as_deref() != Some(endpoint.host.as_str()) {
+        println!("Sidecar answering on {}:{}", endpoint.host, endpoint.port);
+        *resolved = Some(endpoint.host.clone());
+    }
+}
+
+fn api_url(endpoint: &str) -> String {
+    active_endpoint().url(endpoint)
+}
+
+// Demo/read-only mode: every non-GET request is refused before it reaches the sidecar
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+pub fn set_read_only(enabled: bool) {
+    READ_ONLY.store(enabled, Ordering::SeqCst);
+}
+
+pub fn is_read_only() -> bool {
+    READ_ONLY.load(Ordering::SeqCst)
+}
+
+pub fn ensure_writable() -> Result<(), String> {
+    if is_read_only() {
+        Err("Read-only mode is enabled; changes are blocked".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+// Mutating requests currently in flight, so shutdown can wait for them to land
+static IN_FLIGHT_MUTATIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct MutationGuard;
+
+impl MutationGuard {
+    fn new() -> Self {
+        IN_FLIGHT_MUTATIONS.fetch_add(1, Ordering::SeqCst);
+        MutationGuard
+    }
+}
+
+impl Drop for MutationGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT_MUTATIONS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn mutations_in_flight() -> usize {
+    IN_FLIGHT_MUTATIONS.load(Ordering::SeqCst)
+}
+
+/// Blocks until all in-flight mutations finish or `timeout` elapses.
+/// Returns false if mutations were still pending when we gave up.
+pub fn wait_for_mutations(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let pending = IN_FLIGHT_MUTATIONS.load(Ordering::SeqCst);
+        if pending == 0 {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            eprintln!("Giving up on {} in-flight mutation(s) at exit", pending);
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(25));
+    }
+}
+
+/// Waits off the async runtime for in-flight mutations, failing if they don't finish in
+/// time. Used before operations that replace the data files under the sidecar.
+pub async fn drain_mutations(timeout: Duration) -> Result<(), String> {
+    let drained = tokio::task::spawn_blocking(move || wait_for_mutations(timeout))
+        .await
+        .map_err(|e| e.to_string())?;
+    if drained {
+        Ok(())
+    } else {
+        Err("Pending changes are still being saved, try again shortly".to_string())
+    }
+}
+
+// Requests made before the sidecar is up wait here instead of failing on a refused connection
+const MAX_QUEUED_REQUESTS: usize = 64;
+const QUEUE_MAX_WAIT: Duration = Duration::from_secs(30);
+
+#[derive(Clone)]
+enum Readiness {
+    // Lazy start: nothing has been spawned yet and the first request that needs the
+    // sidecar asks for it
+    Deferred,
+    Starting,
+    // A running sidecar is being replaced; requests wait for the new one instead of failing
+    Restarting,
+    Ready,
+    Failed(String),
+}
+
+static READINESS: OnceLock<watch::Sender<Readiness>> = OnceLock::new();
+static QUEUED_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+static START_REQUESTED: OnceLock<Notify> = OnceLock::new();
+
+fn readiness() -> &'static watch::Sender<Readiness> {
+    READINESS.get_or_init(|| watch::channel(Readiness::Starting).0)
+}
+
+fn set_readiness(state: Readiness) {
+    readiness().send_replace(state);
+}
+
+// Moves to `state` only if the current state matches `from`, so a restart that began in
+// the meantime isn't overwritten
+fn transition(from: fn(&Readiness) -> bool, state: Readiness) -> bool {
+    readiness().send_if_modified(|current| {
+        if !from(current) {
+            return false;
+        }
+        *current = state;
+        true
+    })
+}
+
+fn is_pending(state: &Readiness) -> bool {
+    matches!(
+        state,
+        Readiness::Deferred | Readiness::Starting | Readiness::Restarting
+    )
+}
+
+fn start_requested() -> &'static Notify {
+    START_REQUESTED.get_or_init(Notify::new)
+}
+
+/// Holds off starting the sidecar until something asks for it (see `request_start`).
+pub fn defer_start() {
+    set_readiness(Readiness::Deferred);
+}
+
+/// Whether a lazy start is still waiting to be triggered. Background pollers check this
+/// so they don't start the sidecar themselves.
+pub fn is_deferred() -> bool {
+    matches!(*readiness().borrow(), Readiness::Deferred)
+}
+
+/// Resolves once the sidecar is no longer waiting for a lazy start, without asking for one.
+/// For scheduled work that shouldn't be what starts it.
+pub async fn wait_until_started() {
+    let mut rx = readiness().subscribe();
+    let _ = rx
+        .wait_for(|state| !matches!(state, Readiness::Deferred))
+        .await;
+}
+
+/// Asks for a deferred sidecar to be started; does nothing otherwise.
+pub fn request_start() {
+    if is_deferred() {
+        // Stores a permit, so a request made before anyone waits isn't lost
+        start_requested().notify_one();
+    }
+}
+
+/// Resolves once a deferred start has been requested.
+pub async fn start_request() {
+    start_requested().notified().await;
+}
+
+struct QueueSlot;
+
+impl Drop for QueueSlot {
+    fn drop(&mut self) {
+        QUEUED_REQUESTS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Parks the caller until the sidecar is ready (the queue is flushed in one go when
+// `wait_until_ready` succeeds). Rejects if startup failed, the wait runs too long, or
+// too many requests are already waiting.
+async fn await_ready() -> Result<(), String> {
+    let mut rx = readiness().subscribe();
+    match &*rx.borrow() {
+        Readiness::Ready => return Ok(()),
+        Readiness::Failed(e) => return Err(format!("Sidecar failed to start: {}", e)),
+        Readiness::Deferred => request_start(),
+        Readiness::Starting | Readiness::Restarting => {}
+    }
+
+    if QUEUED_REQUESTS.fetch_add(1, Ordering::SeqCst) >= MAX_QUEUED_REQUESTS {
+        QUEUED_REQUESTS.fetch_sub(1, Ordering::SeqCst);
+        return Err("Sidecar is still starting and too many requests are waiting".to_string());
+    }
+    let _slot = QueueSlot;
+
+    let waited =
+        tokio::time::timeout(QUEUE_MAX_WAIT, rx.wait_for(|state| !is_pending(state))).await;
+    match waited {
+        Err(_) => Err(format!(
+            "Sidecar did not become ready within {} s",
+            QUEUE_MAX_WAIT.as_secs()
+        )),
+        Ok(Err(_)) => Err("Sidecar readiness channel closed".to_string()),
+        Ok(Ok(state)) => match &*state {
+            Readiness::Failed(e) => Err(format!("Sidecar failed to start: {}", e)),
+            _ => Ok(()),
+        },
+    }
+}
+
+// Sends `builder`, and if the connection is refused because a restart began after the
+// request passed the readiness gate, waits for the new sidecar and sends it once more.
+// A refused connection means the request never reached the old process, so resending is safe.
+async fn send_retrying(builder: reqwest::RequestBuilder) -> Result<reqwest::Response, String> {
+    // Only pay for building a copy of the request when someone is watching
+    let traced = http_trace::enabled()
+        .then(|| builder.try_clone()?.build().ok())
+        .flatten()
+        .map(|request| http_trace::describe(&request));
+    let started = Instant::now();
+    let result = send_with_retry(builder).await;
+    if let Some(traced) = traced {
+        http_trace::log(traced, &result, started.elapsed());
+    }
+    result
+}
+
+async fn send_with_retry(builder: reqwest::RequestBuilder) -> Result<reqwest::Response, String> {
+    let retry = builder.try_clone();
+    let result = builder.send().await;
+    record_outcome(&result);
+    match result {
+        Err(e) if e.is_connect() && is_pending(&readiness().borrow()) => {
+            let Some(retry) = retry else {
+                return Err(e.to_string());
+            };
+            await_ready().await?;
+            let result = retry.send().await;
+            record_outcome(&result);
+            result.map_err(|e| e.to_string())
+        }
+        result => result.map_err(|e| e.to_string()),
+    }
+}
+
+// Most recent stderr lines kept for the diagnostics panel
+const STDERR_BUFFER_LINES: usize = 200;
+
 pub struct PythonSidecar {
+    endpoint: ApiEndpoint,
     process: Mutex<Option<Child>>,
+    pid_file: Mutex<Option<PathBuf>>,
+    started_at: Mutex<Option<Instant>>,
+    // What the running child was launched from, for support diagnostics
+    source: Mutex<Option<SidecarSource>>,
+    stderr_lines: Arc<Mutex<VecDeque<String>>>,
+    log: Arc<SidecarLog>,
 }
 
 // Helper to spawn a process hidden on Windows
@@ -28,96 +450,352 @@ fn spawn_hidden(cmd: &mut Command) -> std::io::Result<Child> {
     {
         cmd.creation_flags(CREATE_NO_WINDOW);
     }
+    cmd.stderr(Stdio::piped());
     cmd.spawn()
 }
 
+// Antivirus on Windows can briefly lock a freshly extracted exe; a short retry gets past it
+const AV_SPAWN_ATTEMPTS: u32 = 3;
+const AV_SPAWN_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+fn spawn_sidecar(cmd: &mut Command, source: &SidecarSource) -> std::io::Result<Child> {
+    let retry_lock = cfg!(target_os = "windows") && matches!(source, SidecarSource::Bundled(_));
+    let mut attempt = 1;
+    loop {
+        match spawn_hidden(cmd) {
+            Err(e)
+                if retry_lock
+                    && attempt < AV_SPAWN_ATTEMPTS
+                    && matches!(
+                        e.kind(),
+                        std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::WouldBlock
+                    ) =>
+            {
+                eprintln!(
+                    "Spawn attempt {}/{} of {} failed ({}), retrying...",
+                    attempt, AV_SPAWN_ATTEMPTS, source, e
+                );
+                std::thread::sleep(AV_SPAWN_RETRY_DELAY);
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Where the sidecar command came from, in precedence order
+#[derive(Debug, Clone, PartialEq)]
+pub enum SidecarSource {
+    /// Forced via `OVELO_SIDECAR`
+    Override(PathBuf),
+    /// Packaged `ovelo_server` executable (production)
+    Bundled(PathBuf),
+    /// `python/sidecar.py` run through the interpreter (development)
+    DevScript(PathBuf),
+}
+
+impl SidecarSource {
+    pub fn path(&self) -> &Path {
+        match self {
+            SidecarSource::Override(path)
+            | SidecarSource::Bundled(path)
+            | SidecarSource::DevScript(path) => path,
+        }
+    }
+
+    fn command(&self) -> Command {
+        let path = self.path();
+        if path.extension().is_some_and(|ext| ext == "py") {
+            // An unresolved interpreter leaves `python` so the spawn error surfaces as before
+            let python = interpreter::resolve()
+                .map(|info| info.path)
+                .unwrap_or_else(|_| PathBuf::from("python"));
+            let mut command = Command::new(python);
+            command.arg(path);
+            command
+        } else {
+            Command::new(path)
+        }
+    }
+}
+
+impl std::fmt::Display for SidecarSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SidecarSource::Override(path) => write!(f, "OVELO_SIDECAR override {:?}", path),
+            SidecarSource::Bundled(path) => write!(f, "bundled executable {:?}", path),
+            SidecarSource::DevScript(path) => write!(f, "dev script {:?}", path),
+        }
+    }
+}
+
+/// Which kind of sidecar is serving requests, as reported to support.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarSourceInfo {
+    /// "bundled", "dev-script", "override" or "remote"
+    kind: &'static str,
+    path: String,
+}
+
+#[derive(Debug)]
+pub enum SidecarResolveError {
+    /// `OVELO_SIDECAR` points at a file that doesn't exist
+    OverrideMissing(PathBuf),
+    /// None of the bundled or dev locations had a usable sidecar
+    NotFound(Vec<PathBuf>),
+}
+
+impl std::fmt::Display for SidecarResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SidecarResolveError::OverrideMissing(path) => {
+                write!(f, "OVELO_SIDECAR points to missing file {:?}", path)
+            }
+            SidecarResolveError::NotFound(checked) => {
+                write!(f, "No sidecar found (checked {:?})", checked)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SidecarResolveError {}
+
+// Bundled executable locations, exe-relative first (NSIS installs) then the resource dir
+fn bundled_candidates(app_handle: &tauri::AppHandle) -> Vec<PathBuf> {
+    let binary_name = if cfg!(windows) {
+        "ovelo_server.exe"
+    } else {
+        "ovelo_server"
+    };
+
+    let mut paths = Vec::new();
+    if let Some(parent) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(PathBuf::from))
+    {
+        paths.push(parent.join("resources").join("backend").join(binary_name));
+        paths.push(parent.join("backend").join(binary_name));
+        paths.push(parent.join(binary_name));
+    }
+    if let Ok(resource_dir) = app_handle.path().resource_dir() {
+        paths.push(resource_dir.join("backend").join(binary_name));
+        paths.push(resource_dir.join(binary_name));
+    }
+    paths
+}
+
+// Repository root this binary was built from, where `python/` lives in development
+fn project_root() -> &'static Path {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir)
+}
+
+fn dev_script_candidates() -> Vec<PathBuf> {
+    vec![project_root().join("python").join("sidecar.py")]
+}
+
+/// Directory the sidecar runs in: `OVELO_WORKDIR` if set, the project root for the dev
+/// script, else the executable's own directory. Never inherited from how Ovelo was launched.
+fn sidecar_workdir(source: &SidecarSource) -> PathBuf {
+    if let Some(dir) = std::env::var_os("OVELO_WORKDIR").map(PathBuf::from) {
+        if !dir.is_dir() {
+            eprintln!("OVELO_WORKDIR {:?} is not a directory", dir);
+        }
+        return dir;
+    }
+    match source {
+        SidecarSource::DevScript(_) => project_root().to_path_buf(),
+        SidecarSource::Override(path) | SidecarSource::Bundled(path) => path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| project_root().to_path_buf()),
+    }
+}
+
+// Pure precedence logic: override, then bundled, then dev script, skipping paths in `exclude`
+fn select_sidecar(
+    override_path: Option<PathBuf>,
+    bundled: &[PathBuf],
+    dev_scripts: &[PathBuf],
+    exclude: &[PathBuf],
+) -> Result<SidecarSource, SidecarResolveError> {
+    if let Some(path) = override_path {
+        println!(
+            "OVELO_SIDECAR override: {:?} exists={}",
+            path,
+            path.exists()
+        );
+        return if path.exists() {
+            Ok(SidecarSource::Override(path))
+        } else {
+            Err(SidecarResolveError::OverrideMissing(path))
+        };
+    }
+
+    let candidates = bundled
+        .iter()
+        .cloned()
+        .map(SidecarSource::Bundled)
+        .chain(dev_scripts.iter().cloned().map(SidecarSource::DevScript));
+
+    let mut checked = Vec::new();
+    for candidate in candidates {
+        let path = candidate.path();
+        if exclude.iter().any(|skipped| skipped == path) {
+            continue;
+        }
+        println!("Checking {} exists={}", candidate, path.exists());
+        if path.exists() {
+            return Ok(candidate);
+        }
+        checked.push(path.to_path_buf());
+    }
+    Err(SidecarResolveError::NotFound(checked))
+}
+
+/// Picks the command to launch the sidecar with and why it was chosen.
+/// `exclude` lists paths that already failed to spawn so the next candidate is tried.
+pub fn resolve_sidecar_command(
+    app_handle: &tauri::AppHandle,
+    exclude: &[PathBuf],
+) -> Result<(Command, SidecarSource), SidecarResolveError> {
+    let override_path = std::env::var_os("OVELO_SIDECAR").map(PathBuf::from);
+    let source = select_sidecar(
+        override_path,
+        &bundled_candidates(app_handle),
+        &dev_script_candidates(),
+        exclude,
+    )?;
+    println!("Using {}", source);
+    Ok((source.command(), source))
+}
+
 impl PythonSidecar {
     pub fn new() -> Self {
         Self {
+            endpoint: api_endpoint().clone(),
             process: Mutex::new(None),
+            pid_file: Mutex::new(None),
+            started_at: Mutex::new(None),
+            source: Mutex::new(None),
+            stderr_lines: Arc::new(Mutex::new(VecDeque::with_capacity(STDERR_BUFFER_LINES))),
+            log: Arc::new(SidecarLog::new()),
         }
     }
 
-    pub fn start(&self, app_handle: &tauri::AppHandle) {
-        println!("Starting Python sidecar...");
+    // Forwards the child's stderr to ours while keeping the last few lines in memory
+    fn capture_stderr(&self, stderr: ChildStderr) {
+        let lines = self.stderr_lines.clone();
+        let log = self.log.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                eprintln!("[sidecar] {}", line);
+                log.write_line(&line);
+                let mut lines = lines.lock().unwrap();
+                if lines.len() == STDERR_BUFFER_LINES {
+                    lines.pop_front();
+                }
+                lines.push_back(line);
+            }
+        });
+    }
 
-        let mut child_result = Err(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "No sidecar found",
-        ));
+    pub fn log(&self) -> &SidecarLog {
+        &self.log
+    }
 
-        // 1. Try Bundled Executable (Production)
-        // Check multiple possible locations for the backend executable
-        if let Ok(resource_dir) = app_handle.path().resource_dir() {
-            let binary_name = if cfg!(windows) {
-                "ovelo_server.exe"
-            } else {
-                "ovelo_server"
-            };
+    pub fn recent_errors(&self) -> Vec<String> {
+        self.stderr_lines.lock().unwrap().iter().cloned().collect()
+    }
 
-            // List of paths to check in order of preference
-            let paths_to_check = vec![
-                resource_dir.join("backend").join(binary_name),
-                resource_dir.join(binary_name),
-            ];
-
-            // Also check in the exe's directory (for NSIS installs)
-            if let Ok(exe_dir) = std::env::current_exe() {
-                if let Some(parent) = exe_dir.parent() {
-                    let paths_with_exe_dir: Vec<PathBuf> = vec![
-                        parent.join("resources").join("backend").join(binary_name),
-                        parent.join("backend").join(binary_name),
-                        parent.join(binary_name),
-                    ];
-                    for path in paths_with_exe_dir {
-                        println!(
-                            "Checking exe-relative path: {:?} exists={}",
-                            path,
-                            path.exists()
-                        );
-                        if path.exists() {
-                            println!("Found bundled sidecar at exe-relative: {:?}", path);
-                            child_result = spawn_hidden(&mut Command::new(path));
-                            break;
-                        }
-                    }
-                }
-            }
+    pub fn clear_errors(&self) {
+        self.stderr_lines.lock().unwrap().clear();
+    }
 
-            // Check resource_dir paths if not found yet
-            if child_result.is_err() {
-                for path in paths_to_check {
-                    println!(
-                        "Checking resource path: {:?} exists={}",
-                        path,
-                        path.exists()
+    pub fn start(&self, app_handle: &tauri::AppHandle) {
+        if !matches!(*readiness().borrow(), Readiness::Restarting) {
+            set_readiness(Readiness::Starting);
+        }
+        if self.endpoint.is_remote() {
+            println!(
+                "Using remote sidecar at {}:{}, not spawning a local one",
+                self.endpoint.host, self.endpoint.port
+            );
+            return;
+        }
+        println!("Starting Python sidecar...");
+
+        // Try candidates in precedence order; if one fails to spawn, fall through to the next
+        let mut failed: Vec<PathBuf> = Vec::new();
+        let child_result = loop {
+            let (mut command, source) = match resolve_sidecar_command(app_handle, &failed) {
+                Ok(resolved) => resolved,
+                Err(e) => break Err(std::io::Error::new(std::io::ErrorKind::NotFound, e)),
+            };
+            if let SidecarSource::Bundled(path) = &source {
+                if let Err(failure) = integrity::verify_bundled(path) {
+                    eprintln!(
+                        "Refusing to launch {}: SHA-256 {} does not match expected {}",
+                        source, failure.actual, failure.expected
                     );
-                    if path.exists() {
-                        println!("Found bundled sidecar at: {:?}", path);
-                        child_result = spawn_hidden(&mut Command::new(path));
-                        break;
-                    }
+                    let _ = app_handle.emit("sidecar-integrity-failed", failure);
+                    failed.push(path.clone());
+                    continue;
                 }
             }
-        }
-
-        // 2. Fallback to Python Script (Development)
-        if child_result.is_err() {
-            let mut script_path = PathBuf::from("python/sidecar.py");
-            if !script_path.exists() {
-                script_path = PathBuf::from("../python/sidecar.py");
+            let workdir = sidecar_workdir(&source);
+            println!("Using working dir {:?}", workdir);
+            command.current_dir(workdir);
+            if let Some(dir) = paths::sidecar_data_dir(app_handle) {
+                println!("Using data dir {:?}", dir);
+                command.env("OVELO_DATA_DIR", dir);
             }
-
-            if script_path.exists() {
-                println!("Found dev script: {:?}", script_path);
-                child_result = spawn_hidden(&mut Command::new("python").arg(script_path));
+            match spawn_sidecar(&mut command, &source) {
+                Ok(child) => {
+                    if !cfg!(debug_assertions) && matches!(source, SidecarSource::DevScript(_)) {
+                        eprintln!("==================================================");
+                        eprintln!("WARNING: release build is running the dev script");
+                        eprintln!("{:?}. The bundled server was not found.", source.path());
+                        eprintln!("==================================================");
+                    }
+                    *self.source.lock().unwrap() = Some(source);
+                    break Ok(child);
+                }
+                Err(e) => {
+                    eprintln!("Failed to spawn {}: {}", source, e);
+                    if matches!(source, SidecarSource::Override(_)) {
+                        break Err(e);
+                    }
+                    failed.push(source.path().to_path_buf());
+                }
             }
-        }
+        };
 
         match child_result {
-            Ok(child) => {
+            Ok(mut child) => {
                 println!("Python sidecar started with PID: {}", child.id());
 
+                if let Err(e) = paths::log_dir(app_handle).and_then(|dir| self.log.open(&dir)) {
+                    eprintln!("Failed to open sidecar log: {}", e);
+                }
+                if let Some(stderr) = child.stderr.take() {
+                    self.capture_stderr(stderr);
+                }
+
+                match paths::pid_file(app_handle) {
+                    // Sidecar PID, then ours, so startup cleanup can tell a crash leftover
+                    // from a sidecar another running instance owns
+                    Ok(path) => match std::fs::write(
+                        &path,
+                        format!("{}\n{}", child.id(), std::process::id()),
+                    ) {
+                        Ok(()) => *self.pid_file.lock().unwrap() = Some(path),
+                        Err(e) => eprintln!("Failed to write PID file: {}", e),
+                    },
+                    Err(e) => eprintln!("Failed to resolve PID file: {}", e),
+                }
+
                 #[cfg(target_os = "windows")]
                 {
                     unsafe {
@@ -157,6 +835,7 @@ impl PythonSidecar {
                 }
 
                 *self.process.lock().unwrap() = Some(child);
+                *self.started_at.lock().unwrap() = Some(Instant::now());
             }
             Err(e) => {
                 eprintln!("Failed to start python sidecar: {}", e);
@@ -164,27 +843,320 @@ impl PythonSidecar {
         }
     }
 
+    /// Polls the sidecar until its HTTP server answers. Any response counts as ready;
+    /// only connection failures are retried. Bails out early if the process has exited.
+    /// The outcome releases (or rejects) any requests queued while starting.
+    pub async fn wait_until_ready(&self) -> Result<(), String> {
+        let result = self.poll_ready().await;
+        match &result {
+            Ok(()) => set_readiness(Readiness::Ready),
+            Err(e) => set_readiness(Readiness::Failed(e.clone())),
+        }
+        result
+    }
+
+    async fn poll_ready(&self) -> Result<(), String> {
+        let started = Instant::now();
+        let client = client();
+        let candidates = self.endpoint.candidates();
+
+        let mut delay_ms = READY_BASE_DELAY_MS;
+        let mut attempts = 0;
+        while attempts < READY_MAX_ATTEMPTS {
+            attempts += 1;
+
+            if let Some(status) = self.exit_status() {
+                return Err(format!(
+                    "Sidecar exited ({}) before becoming ready after {} attempts in {} ms",
+                    status,
+                    attempts,
+                    started.elapsed().as_millis()
+                ));
+            }
+
+            let mut answered = None;
+            for candidate in &candidates {
+                let probe = client
+                    .get(candidate.url(READY_ENDPOINT))
+                    .timeout(READY_PROBE_TIMEOUT);
+                if let Ok(res) = probe.send().await {
+                    answered = Some((candidate, res));
+                    break;
+                }
+            }
+            if let Some((candidate, res)) = answered {
+                remember_host(candidate);
+                if !is_migrating(res).await {
+                    println!(
+                        "Python sidecar ready after {} attempts in {} ms",
+                        attempts,
+                        started.elapsed().as_millis()
+                    );
+                    return Ok(());
+                }
+                // Alive but upgrading its schema: keep waiting without using up attempts
+                if started.elapsed() >= MIGRATION_MAX_WAIT {
+                    return Err(format!(
+                        "Sidecar still migrating after {} s",
+                        MIGRATION_MAX_WAIT.as_secs()
+                    ));
+                }
+                attempts -= 1;
+                tokio::time::sleep(Duration::from_millis(READY_MAX_DELAY_MS)).await;
+                continue;
+            }
+
+            let jitter_ms = rand::thread_rng().gen_range(READY_JITTER_MS);
+            tokio::time::sleep(Duration::from_millis(delay_ms + jitter_ms)).await;
+            delay_ms = (delay_ms * 2).min(READY_MAX_DELAY_MS);
+        }
+
+        Err(format!(
+            "Sidecar not ready after {} attempts in {} ms",
+            attempts,
+            started.elapsed().as_millis()
+        ))
+    }
+
+    /// Starts a deferred sidecar if need be and waits until it's ready. Safe to call any
+    /// number of times; callers after the first just wait.
+    pub async fn ensure_started(&self) -> Result<(), String> {
+        request_start();
+        await_ready().await
+    }
+
+    /// Records that startup was abandoned before spawning, so queued requests fail with `reason`.
+    pub fn fail_startup(&self, reason: String) {
+        set_readiness(Readiness::Failed(reason));
+    }
+
+    /// Where the sidecar in use came from, or None before one has been launched.
+    pub fn source_info(&self) -> Option<SidecarSourceInfo> {
+        if self.endpoint.is_remote() {
+            return Some(SidecarSourceInfo {
+                kind: "remote",
+                path: format!("{}:{}", self.endpoint.host, self.endpoint.port),
+            });
+        }
+        let source = self.source.lock().unwrap();
+        let source = source.as_ref()?;
+        let kind = match source {
+            SidecarSource::Override(_) => "override",
+            SidecarSource::Bundled(_) => "bundled",
+            SidecarSource::DevScript(_) => "dev-script",
+        };
+        Some(SidecarSourceInfo {
+            kind,
+            path: source.path().display().to_string(),
+        })
+    }
+
+    /// PID and spawn time of the child, if it is still running.
+    pub fn running_since(&self) -> Option<(u32, Instant)> {
+        let mut process_guard = self.process.lock().unwrap();
+        let child = process_guard.as_mut()?;
+        if !matches!(child.try_wait(), Ok(None)) {
+            return None;
+        }
+        let started_at = (*self.started_at.lock().unwrap())?;
+        Some((child.id(), started_at))
+    }
+
+    // Returns the exit status if the child has already terminated
+    fn exit_status(&self) -> Option<ExitStatus> {
+        let mut process_guard = self.process.lock().unwrap();
+        process_guard
+            .as_mut()
+            .and_then(|child| child.try_wait().ok().flatten())
+    }
+
+    /// Whether the sidecar is up, from the readiness state kept by startup, restarts and the
+    /// health monitor; no request is made.
+    pub fn is_ready(&self) -> bool {
+        matches!(*readiness().borrow(), Readiness::Ready)
+    }
+
+    /// `start` for async callers. Spawning can block (hashing the bundled binary, retrying
+    /// past an antivirus lock), so it runs on the blocking pool instead of a runtime worker.
+    pub async fn start_async(self: &Arc<Self>, app_handle: &tauri::AppHandle) {
+        let (sidecar, app_handle) = (self.clone(), app_handle.clone());
+        if let Err(e) = tokio::task::spawn_blocking(move || sidecar.start(&app_handle)).await {
+            eprintln!("Sidecar start task failed: {}", e);
+        }
+    }
+
+    /// `restart` for async callers; see `start_async`.
+    pub async fn restart_async(self: &Arc<Self>, app_handle: &tauri::AppHandle) {
+        let (sidecar, app_handle) = (self.clone(), app_handle.clone());
+        if let Err(e) = tokio::task::spawn_blocking(move || sidecar.restart(&app_handle)).await {
+            eprintln!("Sidecar restart task failed: {}", e);
+        }
+    }
+
     pub fn stop(&self) {
+        // Anything sent from here until the next successful start waits in the queue
+        set_readiness(Readiness::Starting);
+        self.kill_process();
+    }
+
+    /// Replaces the running sidecar. Requests made meanwhile wait for the new process, the
+    /// same as during startup; the state clears once `wait_until_ready` succeeds.
+    pub fn restart(&self, app_handle: &tauri::AppHandle) {
+        set_readiness(Readiness::Restarting);
+        self.kill_process();
+        self.start(app_handle);
+    }
+
+    fn kill_process(&self) {
         let mut process_guard = self.process.lock().unwrap();
+        self.started_at.lock().unwrap().take();
         if let Some(mut child) = process_guard.take() {
             println!("Stopping Python sidecar...");
             let _ = child.kill();
         }
+        if let Some(path) = self.pid_file.lock().unwrap().take() {
+            let _ = std::fs::remove_file(path);
+        }
     }
 }
 
+/// Watches a ready sidecar for a crashed child or a server that stopped answering, marks it
+/// failed and emits `sidecar-failed`. A server it marked failed that answers again (e.g. a
+/// remote backend back online) is marked ready and `sidecar-ready` is emitted.
+pub fn start_health_monitor(app_handle: tauri::AppHandle, sidecar: Arc<PythonSidecar>) {
+    tauri::async_runtime::spawn(async move {
+        let mut failures = 0;
+        let mut marked_offline = false;
+        loop {
+            tokio::time::sleep(HEALTH_INTERVAL).await;
+            let ready = matches!(*readiness().borrow(), Readiness::Ready);
+            if !ready && !marked_offline {
+                failures = 0;
+                continue;
+            }
+
+            if let Some(status) = sidecar.exit_status() {
+                let reason = format!("Sidecar exited ({})", status);
+                if ready
+                    && transition(
+                        |state| matches!(state, Readiness::Ready),
+                        Readiness::Failed(reason.clone()),
+                    )
+                {
+                    eprintln!("Python sidecar exited ({})", status);
+                    let _ = app_handle.emit("sidecar-failed", reason);
+                }
+                // Nothing left to answer probes; a restart resets the state
+                marked_offline = false;
+                continue;
+            }
+
+            let alive = probe(READY_ENDPOINT, HEALTH_PROBE_TIMEOUT).await.is_ok();
+            if alive {
+                failures = 0;
+                if marked_offline {
+                    marked_offline = false;
+                    if transition(
+                        |state| matches!(state, Readiness::Failed(_)),
+                        Readiness::Ready,
+                    ) {
+                        println!("Python sidecar is answering again");
+                        let _ = app_handle.emit("sidecar-ready", ());
+                    }
+                }
+                continue;
+            }
+            if !ready {
+                continue;
+            }
+            failures += 1;
+            if failures >= HEALTH_MAX_FAILURES {
+                let reason = format!("Sidecar not responding after {} health checks", failures);
+                if transition(
+                    |state| matches!(state, Readiness::Ready),
+                    Readiness::Failed(reason.clone()),
+                ) {
+                    eprintln!("{}", reason);
+                    marked_offline = true;
+                    let _ = app_handle.emit("sidecar-failed", reason);
+                }
+                failures = 0;
+            }
+        }
+    });
+}
+
+// The health endpoint answers `{ "status": "migrating" }` while the DB schema is upgraded
+async fn is_migrating(res: reqwest::Response) -> bool {
+    res.json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|json| {
+            json.get("status")
+                .and_then(|s| s.as_str())
+                .map(String::from)
+        })
+        .is_some_and(|status| status == "migrating")
+}
+
+/// Whether the sidecar is still starting up or restarting (requests are being queued).
+pub fn is_starting() -> bool {
+    is_pending(&readiness().borrow())
+}
+
+/// GET that skips the startup queue, for status polls made while the sidecar is starting.
+pub async fn get_unqueued(endpoint: &str, timeout: Duration) -> Result<serde_json::Value, String> {
+    let res = client()
+        .get(api_url(endpoint))
+        .timeout(timeout)
+        .send()
+        .await;
+    record_outcome(&res);
+    let res = res.map_err(|e| e.to_string())?;
+    if res.status().is_success() {
+        res.json().await.map_err(|e| e.to_string())
+    } else {
+        Err(api_error(res).await)
+    }
+}
+
+// Builds the error text for a non-2xx response. Flask handlers usually send
+// `{ "error": ... }` or `{ "message": ... }`; surface that, else fall back to the status line.
+fn error_message(status: reqwest::StatusCode, body: &str) -> String {
+    let detail = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| {
+            ["message", "error"]
+                .iter()
+                .find_map(|key| json.get(key).and_then(|v| v.as_str()).map(String::from))
+        })
+        .filter(|detail| !detail.trim().is_empty());
+
+    match detail {
+        Some(detail) => format!("API Error: {}: {}", status, detail),
+        None => format!("API Error: {}", status),
+    }
+}
+
+async fn api_error(res: reqwest::Response) -> String {
+    let status = res.status();
+    let body = res.text().await.unwrap_or_default();
+    error_message(status, &body)
+}
+
 // Helper to call Python API
 pub async fn call_api(endpoint: &str) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::new();
-    let url = format!("http://127.0.0.1:5006{}", endpoint); // Config.PORT is 5006
+    await_ready().await?;
+    let client = client();
+    let url = api_url(endpoint);
 
-    let res = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    let res = send_retrying(client.get(&url)).await?;
 
     if res.status().is_success() {
         let json: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
         Ok(json)
     } else {
-        Err(format!("API Error: {}", res.status()))
+        Err(api_error(res).await)
     }
 }
 
@@ -192,21 +1164,52 @@ pub async fn post_api(
     endpoint: &str,
     body: serde_json::Value,
 ) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::new();
-    let url = format!("http://127.0.0.1:5006{}", endpoint);
+    send_post(endpoint, body, None, true).await
+}
 
-    let res = client
-        .post(&url)
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+// For slow endpoints (LLM, VACUUM) that need more time than the default
+pub async fn post_api_with_timeout(
+    endpoint: &str,
+    body: serde_json::Value,
+    timeout: Duration,
+) -> Result<serde_json::Value, String> {
+    send_post(endpoint, body, Some(timeout), true).await
+}
+
+// For POSTs that don't save user changes (prewarm, recompute, prune): they aren't counted
+// as in-flight mutations, so they don't hold off compaction or the exit drain
+pub async fn post_api_untracked(
+    endpoint: &str,
+    body: serde_json::Value,
+    timeout: Duration,
+) -> Result<serde_json::Value, String> {
+    send_post(endpoint, body, Some(timeout), false).await
+}
+
+async fn send_post(
+    endpoint: &str,
+    body: serde_json::Value,
+    timeout: Option<Duration>,
+    mutation: bool,
+) -> Result<serde_json::Value, String> {
+    ensure_writable()?;
+    await_ready().await?;
+    let _mutation = mutation.then(MutationGuard::new);
+    let client = client();
+    let url = api_url(endpoint);
+
+    let builder = client.post(&url).json(&body);
+    let builder = match timeout {
+        Some(timeout) => builder.timeout(timeout),
+        None => builder,
+    };
+    let res = send_retrying(builder).await?;
 
     if res.status().is_success() {
         let json: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
         Ok(json)
     } else {
-        Err(format!("API Error: {}", res.status()))
+        Err(api_error(res).await)
     }
 }
 
@@ -215,8 +1218,8 @@ pub async fn call_api_method(
     endpoint: &str,
     body: Option<serde_json::Value>,
 ) -> Result<serde_json::Value, String> {
-    let client = reqwest::Client::new();
-    let url = format!("http://127.0.0.1:5006{}", endpoint);
+    let client = client();
+    let url = api_url(endpoint);
 
     let builder = match method {
         "GET" => client.get(&url),
@@ -225,6 +1228,11 @@ pub async fn call_api_method(
         "PUT" => client.put(&url),
         _ => return Err(format!("Unsupported method: {}", method)),
     };
+    if method != "GET" {
+        ensure_writable()?;
+    }
+    await_ready().await?;
+    let _mutation = (method != "GET").then(MutationGuard::new);
 
     let builder = if let Some(b) = body {
         builder.json(&b)
@@ -232,7 +1240,7 @@ pub async fn call_api_method(
         builder
     };
 
-    let res = builder.send().await.map_err(|e| e.to_string())?;
+    let res = send_retrying(builder).await?;
 
     if res.status().is_success() {
         // Some endpoints might return empty body on success (like 204)
@@ -245,6 +1253,263 @@ pub async fn call_api_method(
             Err(_) => Ok(serde_json::json!({})),
         }
     } else {
-        Err(format!("API Error: {}", res.status()))
+        Err(api_error(res).await)
+    }
+}
+
+// GET that hands back the raw response, for bodies that should be streamed rather than parsed
+pub async fn get_raw(endpoint: &str) -> Result<reqwest::Response, String> {
+    await_ready().await?;
+    let res = send_retrying(client().get(api_url(endpoint))).await?;
+
+    if res.status().is_success() {
+        Ok(res)
+    } else {
+        Err(api_error(res).await)
+    }
+}
+
+// POST that hands back the raw response, for streamed (SSE) replies
+pub async fn post_raw(
+    endpoint: &str,
+    body: serde_json::Value,
+) -> Result<reqwest::Response, String> {
+    ensure_writable()?;
+    await_ready().await?;
+    let res = send_retrying(
+        client()
+            .post(api_url(endpoint))
+            .header(
+                reqwest::header::ACCEPT,
+                "text/event-stream, application/json",
+            )
+            .json(&body),
+    )
+    .await?;
+
+    if res.status().is_success() {
+        Ok(res)
+    } else {
+        Err(api_error(res).await)
+    }
+}
+
+// Bare GET that only reports the status, for latency probes
+pub async fn probe(endpoint: &str, timeout: Duration) -> Result<reqwest::StatusCode, String> {
+    let res = client()
+        .get(api_url(endpoint))
+        .timeout(timeout)
+        .send()
+        .await;
+    record_outcome(&res);
+    res.map(|res| res.status()).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Temp dir with the given files created in it; removed on drop
+    struct Fixture(PathBuf);
+
+    impl Fixture {
+        fn new(files: &[&str]) -> Self {
+            let dir = std::env::temp_dir().join(format!("ovelo-sidecar-{}", uuid::Uuid::new_v4()));
+            std::fs::create_dir_all(&dir).unwrap();
+            for file in files {
+                std::fs::write(dir.join(file), b"").unwrap();
+            }
+            Fixture(dir)
+        }
+
+        fn path(&self, file: &str) -> PathBuf {
+            self.0.join(file)
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn select_sidecar_prefers_override() {
+        let fixture = Fixture::new(&["custom_server", "ovelo_server", "sidecar.py"]);
+        let source = select_sidecar(
+            Some(fixture.path("custom_server")),
+            &[fixture.path("ovelo_server")],
+            &[fixture.path("sidecar.py")],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            source,
+            SidecarSource::Override(fixture.path("custom_server"))
+        );
+    }
+
+    #[test]
+    fn select_sidecar_missing_override_does_not_fall_back() {
+        let fixture = Fixture::new(&["ovelo_server"]);
+        let result = select_sidecar(
+            Some(fixture.path("custom_server")),
+            &[fixture.path("ovelo_server")],
+            &[],
+            &[],
+        );
+        assert!(matches!(
+            result,
+            Err(SidecarResolveError::OverrideMissing(path)) if path == fixture.path("custom_server")
+        ));
+    }
+
+    #[test]
+    fn select_sidecar_prefers_bundled_over_dev_script() {
+        let fixture = Fixture::new(&["ovelo_server", "sidecar.py"]);
+        let source = select_sidecar(
+            None,
+            &[fixture.path("missing_server"), fixture.path("ovelo_server")],
+            &[fixture.path("sidecar.py")],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(source, SidecarSource::Bundled(fixture.path("ovelo_server")));
+    }
+
+    #[test]
+    fn select_sidecar_falls_back_to_dev_script() {
+        let fixture = Fixture::new(&["sidecar.py"]);
+        let source = select_sidecar(
+            None,
+            &[fixture.path("ovelo_server")],
+            &[fixture.path("sidecar.py")],
+            &[],
+        )
+        .unwrap();
+        assert_eq!(source, SidecarSource::DevScript(fixture.path("sidecar.py")));
+    }
+
+    #[test]
+    fn select_sidecar_skips_candidates_that_failed_to_spawn() {
+        let fixture = Fixture::new(&["ovelo_server", "sidecar.py"]);
+        let source = select_sidecar(
+            None,
+            &[fixture.path("ovelo_server")],
+            &[fixture.path("sidecar.py")],
+            &[fixture.path("ovelo_server")],
+        )
+        .unwrap();
+        assert_eq!(source, SidecarSource::DevScript(fixture.path("sidecar.py")));
+    }
+
+    #[test]
+    fn select_sidecar_reports_every_path_checked() {
+        let fixture = Fixture::new(&[]);
+        let result = select_sidecar(
+            None,
+            &[fixture.path("ovelo_server")],
+            &[fixture.path("sidecar.py")],
+            &[],
+        );
+        assert!(matches!(
+            result,
+            Err(SidecarResolveError::NotFound(checked))
+                if checked == [fixture.path("ovelo_server"), fixture.path("sidecar.py")]
+        ));
+    }
+
+    fn endpoint(host: &str, port: u16) -> ApiEndpoint {
+        ApiEndpoint {
+            host: host.to_string(),
+            port,
+        }
+    }
+
+    #[test]
+    fn url_uses_configured_host_and_port() {
+        assert_eq!(
+            endpoint("192.168.1.20", 8080).url("/health"),
+            "http://192.168.1.20:8080/health"
+        );
+        assert_eq!(
+            endpoint("qa-box.local", 5006).url("/today_state"),
+            "http://qa-box.local:5006/today_state"
+        );
+    }
+
+    #[test]
+    fn url_brackets_ipv6_hosts() {
+        assert_eq!(
+            endpoint("::1", 5006).url("/health"),
+            "http://[::1]:5006/health"
+        );
+        assert_eq!(
+            endpoint("[::1]", 5006).url("/health"),
+            "http://[::1]:5006/health"
+        );
+    }
+
+    #[test]
+    fn candidates_try_configured_host_then_loopbacks() {
+        let hosts = |e: ApiEndpoint| -> Vec<String> {
+            e.candidates().into_iter().map(|c| c.host).collect()
+        };
+        assert_eq!(hosts(endpoint("127.0.0.1", 5006)), ["127.0.0.1", "::1"]);
+        assert_eq!(
+            hosts(endpoint("localhost", 5006)),
+            ["localhost", "127.0.0.1", "::1"]
+        );
+        assert_eq!(
+            endpoint("localhost", 5006)
+                .candidates()
+                .iter()
+                .map(|c| c.url("/health"))
+                .collect::<Vec<_>>(),
+            [
+                "http://localhost:5006/health",
+                "http://127.0.0.1:5006/health",
+                "http://[::1]:5006/health"
+            ]
+        );
+        // A remote server is only ever reached where it was configured
+        assert_eq!(hosts(endpoint("192.168.1.20", 8080)), ["192.168.1.20"]);
+    }
+
+    #[test]
+    fn error_message_uses_json_error_fields() {
+        let status = reqwest::StatusCode::BAD_REQUEST;
+        assert_eq!(
+            error_message(status, r#"{"error": "date is required"}"#),
+            "API Error: 400 Bad Request: date is required"
+        );
+        assert_eq!(
+            error_message(
+                status,
+                r#"{"message": "Invalid persona", "error": "bad_request"}"#
+            ),
+            "API Error: 400 Bad Request: Invalid persona"
+        );
+    }
+
+    #[test]
+    fn error_message_falls_back_to_status_line() {
+        let status = reqwest::StatusCode::INTERNAL_SERVER_ERROR;
+        let expected = "API Error: 500 Internal Server Error";
+        assert_eq!(
+            error_message(status, "Traceback (most recent call last): ..."),
+            expected
+        );
+        assert_eq!(error_message(status, ""), expected);
+        assert_eq!(error_message(status, r#"{"error": "  "}"#), expected);
+        assert_eq!(error_message(status, r#"{"error": {"code": 3}}"#), expected);
+    }
+
+    #[test]
+    fn client_ignores_system_proxies() {
+        // reqwest lists a client's proxies in its Debug output; a default client always has
+        // the system one, so an empty list means proxy lookup is off
+        assert!(format!("{:?}", reqwest::Client::new()).contains("proxies"));
+        assert!(!format!("{:?}", build_client()).contains("proxies"));
     }
 }
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::python_bridge;
+use crate::reflections;
+
+// Reclassifying replays every stored sample, so long ranges take minutes
+const RECLASSIFY_TIMEOUT: Duration = Duration::from_secs(1800);
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+// Ranges longer than this need `confirm_large`
+const LARGE_RANGE_DAYS: i64 = 31;
+
+static RECLASSIFY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ReclassifyProgress {
+    #[serde(default)]
+    done: u64,
+    #[serde(default)]
+    total: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclassifySummary {
+    #[serde(default, alias = "days_processed")]
+    days_processed: u64,
+    #[serde(default, alias = "events_processed")]
+    events_processed: u64,
+    #[serde(default, alias = "events_changed")]
+    events_changed: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct Reclassified {
+    start: String,
+    end: String,
+}
+
+/// Re-runs classification over [start, end] (YYYY-MM-DD, inclusive), e.g. after a classifier
+/// update or new exclusions. Emits `reclassify-progress` while running and `reclassified`
+/// when it's done, so views of the range refetch.
+#[tauri::command]
+pub async fn reclassify_range(
+    app_handle: tauri::AppHandle,
+    start: String,
+    end: String,
+    confirm_large: Option<bool>,
+) -> Result<ReclassifySummary, String> {
+    let (first, last) = (
+        reflections::parse_date(&start)?,
+        reflections::parse_date(&end)?,
+    );
+    let today = chrono::Local::now().date_naive();
+    if first > last {
+        return Err(format!("Range start {} is after end {}", first, last));
+    }
+    if last > today {
+        return Err(format!("Cannot reclassify a future date: {}", last));
+    }
+    let days = (last - first).num_days() + 1;
+    if days > LARGE_RANGE_DAYS && confirm_large != Some(true) {
+        return Err(format!(
+            "Reclassifying {} days requires confirm_large (more than {})",
+            days, LARGE_RANGE_DAYS
+        ));
+    }
+    let _guard = RECLASSIFY_LOCK
+        .try_lock()
+        .map_err(|_| "Reclassification is already running".to_string())?;
+
+    let run = python_bridge::post_api_with_timeout(
+        "/api/reclassify",
+        serde_json::json!({ "start": first.to_string(), "end": last.to_string() }),
+        RECLASSIFY_TIMEOUT,
+    );
+    tokio::pin!(run);
+    let mut progress = tokio::time::interval(PROGRESS_INTERVAL);
+    progress.tick().await;
+    let result = loop {
+        tokio::select! {
+            result = &mut run => break result,
+            _ = progress.tick() => {
+                if let Ok(res) = python_bridge::call_api("/api/reclassify_status").await {
+                    if let Ok(status) = serde_json::from_value::<ReclassifyProgress>(res) {
+                        let _ = app_handle.emit("reclassify-progress", status);
+                    }
+                }
+            }
+        }
+    };
+
+    // Sent even if the run failed partway, since some days may have changed
+    let _ = app_handle.emit(
+        "reclassified",
+        Reclassified {
+            start: first.to_string(),
+            end: last.to_string(),
+        },
+    );
+
+    let summary: ReclassifySummary =
+        serde_json::from_value(result?).map_err(|e| format!("Invalid reclassify result: {}", e))?;
+    println!(
+        "Reclassified {} days: {} of {} events changed",
+        summary.days_processed, summary.events_changed, summary.events_processed
+    );
+    Ok(summary)
+}
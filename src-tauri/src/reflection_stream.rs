@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use futures_util::StreamExt;
+use serde::Serialize;
+use tauri::Emitter;
+use tokio::sync::oneshot;
+
+use crate::personas;
+use crate::python_bridge;
+
+// Cancel handles for generations still streaming, keyed by request id
+static ACTIVE: Mutex<Option<HashMap<String, oneshot::Sender<()>>>> = Mutex::new(None);
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReflectionChunk<'a> {
+    id: &'a str,
+    text: &'a str,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReflectionComplete<'a> {
+    id: &'a str,
+    full_text: &'a str,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReflectionFailed<'a> {
+    id: &'a str,
+    error: &'a str,
+}
+
+// A token event is either plain text or `{ "text": ... }`
+fn token_text(data: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(data)
+        .ok()
+        .and_then(|json| json.get("text").and_then(|t| t.as_str()).map(String::from))
+        .unwrap_or_else(|| data.to_string())
+}
+
+// Forwards each SSE token as `reflection-chunk`, returning the assembled text
+async fn forward_tokens(
+    app_handle: &tauri::AppHandle,
+    id: &str,
+    res: reqwest::Response,
+) -> Result<String, String> {
+    let mut stream = res.bytes_stream();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut full_text = String::new();
+
+    while let Some(chunk) = stream.next().await {
+        buffer.extend_from_slice(&chunk.map_err(|e| e.to_string())?);
+        // Only decode whole events so multi-byte characters split across chunks survive
+        while let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+            let event: Vec<u8> = buffer.drain(..end + 2).collect();
+            let event = String::from_utf8_lossy(&event).replace('\r', "");
+            let data: Vec<&str> = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                .collect();
+            if data.is_empty() {
+                continue;
+            }
+            let data = data.join("\n");
+            if data == "[DONE]" {
+                return Ok(full_text);
+            }
+            let text = token_text(&data);
+            full_text.push_str(&text);
+            let _ = app_handle.emit("reflection-chunk", ReflectionChunk { id, text: &text });
+        }
+    }
+    Ok(full_text)
+}
+
+async fn generate(
+    app_handle: &tauri::AppHandle,
+    id: &str,
+    body: serde_json::Value,
+) -> Result<String, String> {
+    let res = python_bridge::post_raw("/generate_reflection", body).await?;
+    let streamed = res
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("text/event-stream"));
+    if streamed {
+        return forward_tokens(app_handle, id, res).await;
+    }
+
+    // Backend without streaming: the whole reflection arrives as one chunk
+    let json: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
+    let text = json
+        .get("reflection")
+        .and_then(|r| r.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let _ = app_handle.emit("reflection-chunk", ReflectionChunk { id, text: &text });
+    Ok(text)
+}
+
+/// Starts generating a reflection and returns its request id right away. Tokens arrive as
+/// `reflection-chunk` events, followed by `reflection-complete`, `reflection-failed`, or
+/// `reflection-cancelled`.
+#[tauri::command]
+pub async fn generate_reflection_stream(
+    app_handle: tauri::AppHandle,
+    date: String,
+    persona: String,
+) -> Result<String, String> {
+    personas::validate_persona(&persona).await?;
+    python_bridge::ensure_writable()?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    let (cancel_tx, cancel_rx) = oneshot::channel();
+    ACTIVE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(id.clone(), cancel_tx);
+
+    let body = serde_json::json!({ "date": date, "persona": persona, "stream": true });
+    let task_id = id.clone();
+    tauri::async_runtime::spawn(async move {
+        let id = task_id;
+        tokio::select! {
+            result = generate(&app_handle, &id, body) => match result {
+                Ok(full_text) => {
+                    let _ = app_handle.emit(
+                        "reflection-complete",
+                        ReflectionComplete { id: &id, full_text: &full_text },
+                    );
+                }
+                Err(e) => {
+                    let _ = app_handle.emit("reflection-failed", ReflectionFailed { id: &id, error: &e });
+                }
+            },
+            // Dropping the request future closes the connection, which stops the backend stream
+            _ = cancel_rx => {
+                let _ = app_handle.emit("reflection-cancelled", &id);
+            }
+        }
+        if let Some(active) = ACTIVE.lock().unwrap().as_mut() {
+            active.remove(&id);
+        }
+    });
+    Ok(id)
+}
+
+/// Stops a streaming generation. Returns false if it had already finished.
+#[tauri::command]
+pub fn cancel_reflection(id: String) -> bool {
+    let cancel = ACTIVE
+        .lock()
+        .unwrap()
+        .as_mut()
+        .and_then(|active| active.remove(&id));
+    cancel.is_some_and(|tx| tx.send(()).is_ok())
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::python_bridge;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reflection {
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub persona: String,
+    // ISO 8601 local timestamp as written by the backend
+    #[serde(default)]
+    pub timestamp: String,
+}
+
+pub fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date (expected YYYY-MM-DD): {}", date))
+}
+
+// One lock per reflection date: saves for the same day run one at a time, other days don't wait
+static SAVE_LOCKS: Mutex<Option<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> = Mutex::new(None);
+
+/// Runs `save` while holding the lock for `date`.
+pub async fn with_date_lock<F, T>(date: &str, save: F) -> T
+where
+    F: std::future::Future<Output = T>,
+{
+    let lock = SAVE_LOCKS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .entry(date.to_string())
+        .or_default()
+        .clone();
+
+    let result = {
+        let _guard = lock.lock().await;
+        save.await
+    };
+
+    // Drop the entry once nobody else is waiting on it
+    let mut locks = SAVE_LOCKS.lock().unwrap();
+    if let Some(map) = locks.as_mut() {
+        if Arc::strong_count(&lock) == 2 {
+            map.remove(date);
+        }
+    }
+    result
+}
+
+// Longest span a range query may cover
+const MAX_RANGE_DAYS: i64 = 366;
+
+/// Parses an inclusive YYYY-MM-DD range, rejecting reversed or overly long spans.
+pub fn parse_date_range(start: &str, end: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let (start, end) = (parse_date(start)?, parse_date(end)?);
+    if start > end {
+        return Err(format!("Range start {} is after end {}", start, end));
+    }
+    if (end - start).num_days() >= MAX_RANGE_DAYS {
+        return Err(format!("Range can cover at most {} days", MAX_RANGE_DAYS));
+    }
+    Ok((start, end))
+}
+
+/// Full reflection history, newest first.
+pub async fn fetch_history() -> Result<Vec<Reflection>, String> {
+    let res = python_bridge::call_api("/api/reflection_history").await?;
+    let history = res.get("history").cloned().unwrap_or_default();
+    serde_json::from_value(history).map_err(|e| format!("Malformed reflection history: {}", e))
+}
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct PageRequest {
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReflectionPage {
+    pub items: Vec<Reflection>,
+    pub total: usize,
+    pub has_more: bool,
+}
+
+/// One page of history, newest first. Backends without paging support send everything
+/// (no `total`), in which case the page is cut out here.
+pub async fn fetch_page(request: PageRequest) -> Result<ReflectionPage, String> {
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = request.offset;
+    let res = python_bridge::call_api(&format!(
+        "/api/reflection_history?offset={}&limit={}",
+        offset, limit
+    ))
+    .await?;
+
+    let history = res.get("history").cloned().unwrap_or_default();
+    let mut items: Vec<Reflection> = serde_json::from_value(history)
+        .map_err(|e| format!("Malformed reflection history: {}", e))?;
+    let total = match res.get("total").and_then(|t| t.as_u64()) {
+        Some(total) => total as usize,
+        None => {
+            let total = items.len();
+            items = items.into_iter().skip(offset).take(limit).collect();
+            total
+        }
+    };
+    Ok(ReflectionPage {
+        has_more: offset + items.len() < total,
+        items,
+        total,
+    })
+}
+
+#[tauri::command]
+pub async fn get_reflection_history_page(request: PageRequest) -> Result<ReflectionPage, String> {
+    fetch_page(request).await
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn format_markdown(date: &NaiveDate, reflections: &[&Reflection]) -> String {
+    let mut md = format!("# Reflection — {}\n", date.format("%Y-%m-%d"));
+    // Oldest first reads more naturally in a document
+    for reflection in reflections.iter().rev() {
+        let time = reflection.timestamp.get(11..16).unwrap_or("");
+        md.push_str(&format!(
+            "\n## {} {}\n\n{}\n",
+            escape_markdown(&reflection.persona),
+            time,
+            escape_markdown(reflection.text.trim())
+        ));
+    }
+    md
+}
+
+/// Writes the given day's reflections to `dest_path` as Markdown.
+#[tauri::command]
+pub async fn export_reflection_markdown(date: String, dest_path: String) -> Result<(), String> {
+    let day = parse_date(&date)?;
+    let history = fetch_history().await?;
+    let reflections: Vec<&Reflection> = history
+        .iter()
+        .filter(|r| r.timestamp.starts_with(&date))
+        .collect();
+    if reflections.is_empty() {
+        return Err(format!("NotFound: no reflection for {}", date));
+    }
+
+    tokio::fs::write(&dest_path, format_markdown(&day, &reflections))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Clone, Serialize)]
+struct ReflectionsDeleted {
+    count: usize,
+}
+
+// Reflection date from its ISO timestamp (the backend writes local time)
+fn reflection_date(reflection: &Reflection) -> Option<NaiveDate> {
+    reflection
+        .timestamp
+        .get(..10)
+        .and_then(|date| parse_date(date).ok())
+}
+
+// A range missing either bound is open on that side and can reach every reflection
+fn check_range(
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    confirm_all: Option<bool>,
+) -> Result<(), String> {
+    if (start.is_none() || end.is_none()) && confirm_all != Some(true) {
+        return Err("Deleting an open-ended range of reflections requires confirm_all".to_string());
+    }
+    if let (Some(start), Some(end)) = (start, end) {
+        if start > end {
+            return Err(format!("Range start {} is after end {}", start, end));
+        }
+    }
+    Ok(())
+}
+
+/// Deletes reflections dated within [start, end] and returns how many went. Leaving either
+/// bound out leaves that side open (both out deletes everything) and requires `confirm_all`.
+#[tauri::command]
+pub async fn delete_reflections_range(
+    app_handle: tauri::AppHandle,
+    start: Option<String>,
+    end: Option<String>,
+    confirm_all: Option<bool>,
+) -> Result<usize, String> {
+    let start = start.as_deref().map(parse_date).transpose()?;
+    let end = end.as_deref().map(parse_date).transpose()?;
+    check_range(start, end, confirm_all)?;
+
+    let in_range =
+        |date: NaiveDate| start.is_none_or(|s| date >= s) && end.is_none_or(|e| date <= e);
+    let expected = fetch_history()
+        .await?
+        .iter()
+        .filter(|r| reflection_date(r).is_some_and(in_range))
+        .count();
+    if expected == 0 {
+        return Ok(0);
+    }
+
+    let res = python_bridge::post_api(
+        "/api/delete_reflections",
+        serde_json::json!({
+            "start": start.map(|d| d.to_string()),
+            "end": end.map(|d| d.to_string()),
+        }),
+    )
+    .await?;
+    let count = res
+        .get("deleted")
+        .and_then(|d| d.as_u64())
+        .map(|d| d as usize)
+        .unwrap_or(expected);
+
+    let _ = app_handle.emit("reflections-deleted", ReflectionsDeleted { count });
+    Ok(count)
+}
+
+const CONFIG_ENDPOINT: &str = "/api/reflection_config";
+const MIN_MAX_TOKENS: u32 = 64;
+const MAX_MAX_TOKENS: u32 = 4096;
+const MAX_TEMPERATURE: f64 = 2.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReflectionConfig {
+    #[serde(alias = "max_tokens")]
+    max_tokens: u32,
+    temperature: f64,
+}
+
+#[tauri::command]
+pub async fn get_reflection_config() -> Result<ReflectionConfig, String> {
+    let res = python_bridge::call_api(CONFIG_ENDPOINT).await?;
+    serde_json::from_value(res).map_err(|e| format!("Malformed reflection config: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_reflection_config(config: ReflectionConfig) -> Result<ReflectionConfig, String> {
+    if !(MIN_MAX_TOKENS..=MAX_MAX_TOKENS).contains(&config.max_tokens) {
+        return Err(format!(
+            "Max tokens must be between {} and {}",
+            MIN_MAX_TOKENS, MAX_MAX_TOKENS
+        ));
+    }
+    // `contains` is false for NaN, so that is rejected as well
+    if !(0.0..=MAX_TEMPERATURE).contains(&config.temperature) {
+        return Err(format!(
+            "Temperature must be between 0.0 and {:.1}",
+            MAX_TEMPERATURE
+        ));
+    }
+    let body = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    python_bridge::post_api(CONFIG_ENDPOINT, body).await?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    // Counts saves running at once for a date and records the most seen together
+    async fn save(running: &AtomicUsize, most: &AtomicUsize) {
+        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+        most.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        running.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    fn date(s: &str) -> Option<NaiveDate> {
+        Some(parse_date(s).unwrap())
+    }
+
+    #[test]
+    fn bounded_range_needs_no_confirmation() {
+        assert!(check_range(date("2024-05-01"), date("2024-05-31"), None).is_ok());
+        assert!(check_range(date("2024-05-31"), date("2024-05-01"), Some(true)).is_err());
+    }
+
+    #[test]
+    fn open_ended_range_requires_confirm_all() {
+        let today = date("2024-05-31");
+        for (start, end) in [(None, today), (today, None), (None, None)] {
+            assert!(check_range(start, end, None).is_err());
+            assert!(check_range(start, end, Some(false)).is_err());
+            assert!(check_range(start, end, Some(true)).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn saves_for_the_same_date_are_serialized() {
+        let (running, most) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        tokio::join!(
+            with_date_lock("2024-05-01", save(&running, &most)),
+            with_date_lock("2024-05-01", save(&running, &most)),
+        );
+        assert_eq!(most.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn saves_for_different_dates_run_together() {
+        let (running, most) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        tokio::join!(
+            with_date_lock("2024-05-02", save(&running, &most)),
+            with_date_lock("2024-05-03", save(&running, &most)),
+        );
+        assert_eq!(most.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Days, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::local_store::LocalStore;
+use crate::notifications;
+use crate::reflections;
+
+const CONFIG_KEY: &str = "reflection_reminder";
+// Sleeps are capped so DST shifts and system sleep are caught by re-reading the local clock
+const MAX_SLEEP: Duration = Duration::from_secs(15 * 60);
+
+// Bumped on every reschedule; a timer whose generation is stale exits
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReminderConfig {
+    pub enabled: bool,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl Default for ReminderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hour: 21,
+            minute: 0,
+        }
+    }
+}
+
+// Next local wall-clock occurrence of hour:minute after `now`. A time skipped by a DST
+// jump fires at the first valid instant after it.
+fn next_fire(now: DateTime<Local>, time: NaiveTime) -> DateTime<Local> {
+    let mut date = now.date_naive();
+    loop {
+        let naive = date.and_time(time);
+        let candidate = Local.from_local_datetime(&naive).earliest().or_else(|| {
+            Local
+                .from_local_datetime(&(naive + chrono::Duration::hours(1)))
+                .earliest()
+        });
+        if let Some(candidate) = candidate.filter(|c| *c > now) {
+            return candidate;
+        }
+        date = date + Days::new(1);
+    }
+}
+
+async fn reflected_today() -> bool {
+    let today = Local::now().format("%Y-%m-%d").to_string();
+    match reflections::fetch_history().await {
+        Ok(history) => history.iter().any(|r| r.timestamp.starts_with(&today)),
+        // Can't tell; a stray nudge beats a missed one
+        Err(_) => false,
+    }
+}
+
+/// (Re)starts the reminder timer from the stored schedule, replacing any running one.
+pub fn schedule(app_handle: tauri::AppHandle) {
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let config = app_handle
+        .state::<LocalStore>()
+        .get::<ReminderConfig>(CONFIG_KEY)
+        .unwrap_or_default();
+    let Some(time) = NaiveTime::from_hms_opt(config.hour, config.minute, 0) else {
+        return;
+    };
+    if !config.enabled {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let fire_at = next_fire(Local::now(), time);
+            loop {
+                let remaining = (fire_at - Local::now()).to_std().unwrap_or_default();
+                if remaining.is_zero() {
+                    break;
+                }
+                tokio::time::sleep(remaining.min(MAX_SLEEP)).await;
+                if GENERATION.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+            }
+
+            if !reflected_today().await {
+                notifications::notify(&app_handle, "Ovelo", "Time to reflect on your day");
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn set_reflection_reminder(
+    app_handle: tauri::AppHandle,
+    config: ReminderConfig,
+) -> Result<ReminderConfig, String> {
+    if config.hour > 23 || config.minute > 59 {
+        return Err(format!(
+            "Invalid reminder time: {:02}:{:02}",
+            config.hour, config.minute
+        ));
+    }
+    app_handle.state::<LocalStore>().set(CONFIG_KEY, &config)?;
+    schedule(app_handle);
+    Ok(config)
+}
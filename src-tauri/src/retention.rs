@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use crate::local_store::LocalStore;
+use crate::python_bridge;
+
+const POLICY_ENDPOINT: &str = "/api/retention_policy";
+// Today plus yesterday are never pruned, whatever the policy says
+const MIN_RAW_EVENTS_DAYS: u32 = 2;
+const MAX_RAW_EVENTS_DAYS: u32 = 3650;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+const LAST_PRUNE_KEY: &str = "last_prune_at";
+// Pruning is VACUUM-heavy on large databases
+const PRUNE_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    /// Raw events older than this many days are deleted; None keeps them forever
+    #[serde(alias = "raw_events_days")]
+    raw_events_days: Option<u32>,
+    /// Daily summaries survive pruning of the events they were built from
+    #[serde(default = "default_keep_summaries", alias = "keep_summaries")]
+    keep_summaries: bool,
+}
+
+fn default_keep_summaries() -> bool {
+    true
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RetentionPruned {
+    deleted_events: u64,
+    reclaimed_bytes: u64,
+}
+
+async fn fetch_policy() -> Result<RetentionPolicy, String> {
+    let res = python_bridge::call_api(POLICY_ENDPOINT).await?;
+    serde_json::from_value(res).map_err(|e| format!("Malformed retention policy: {}", e))
+}
+
+async fn prune(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let policy = fetch_policy().await?;
+    let Some(days) = policy.raw_events_days else {
+        return Ok(());
+    };
+    let days = days.max(MIN_RAW_EVENTS_DAYS);
+    let before = chrono::Local::now().date_naive() - chrono::Days::new(u64::from(days));
+    let res = python_bridge::post_api_untracked(
+        "/api/prune",
+        serde_json::json!({
+            "before": before.format("%Y-%m-%d").to_string(),
+            "keepSummaries": policy.keep_summaries,
+        }),
+        PRUNE_TIMEOUT,
+    )
+    .await?;
+
+    let count = |key: &str| res.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+    let pruned = RetentionPruned {
+        deleted_events: count("deleted_events"),
+        reclaimed_bytes: count("reclaimed_bytes"),
+    };
+    println!(
+        "Pruned {} events before {}, reclaimed {} bytes",
+        pruned.deleted_events, before, pruned.reclaimed_bytes
+    );
+    let _ = app_handle.emit("retention-pruned", pruned);
+    Ok(())
+}
+
+/// Prunes once a day, counting from the last run so restarts don't reset (or skip) it.
+pub fn start_scheduler(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let since_last = app_handle
+                .state::<LocalStore>()
+                .get::<DateTime<Utc>>(LAST_PRUNE_KEY)
+                .and_then(|last| (Utc::now() - last).to_std().ok())
+                .unwrap_or(PRUNE_INTERVAL);
+            tokio::time::sleep(PRUNE_INTERVAL.saturating_sub(since_last)).await;
+            // Pruning isn't worth starting a lazily started sidecar for
+            python_bridge::wait_until_started().await;
+
+            if let Err(e) = prune(&app_handle).await {
+                eprintln!("Scheduled prune failed: {}", e);
+            }
+            // Recorded even on failure so a persistent error doesn't retry in a tight loop
+            let _ = app_handle
+                .state::<LocalStore>()
+                .set(LAST_PRUNE_KEY, Utc::now());
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_retention_policy() -> Result<RetentionPolicy, String> {
+    fetch_policy().await
+}
+
+#[tauri::command]
+pub async fn set_retention_policy(policy: RetentionPolicy) -> Result<RetentionPolicy, String> {
+    if let Some(days) = policy.raw_events_days {
+        if !(MIN_RAW_EVENTS_DAYS..=MAX_RAW_EVENTS_DAYS).contains(&days) {
+            return Err(format!(
+                "Raw event retention must be between {} and {} days",
+                MIN_RAW_EVENTS_DAYS, MAX_RAW_EVENTS_DAYS
+            ));
+        }
+    }
+    let body = serde_json::to_value(&policy).map_err(|e| e.to_string())?;
+    python_bridge::post_api(POLICY_ENDPOINT, body).await?;
+    Ok(policy)
+}
@@ -0,0 +1,114 @@
+use std::path::Path;
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::paths;
+
+// Leftovers a crash can strand in the app data dir. Only these names are ever removed.
+const LOCK_PREFIXES: &[&str] = &["sidecar", "ovelo"];
+const LOCK_EXTENSIONS: &[&str] = &["lock", "sock"];
+const TEMP_FILES: &[&str] = &["desktop_state.json.tmp", "onboarding_complete.tmp"];
+
+fn is_ours(name: &str) -> bool {
+    if TEMP_FILES.contains(&name) {
+        return true;
+    }
+    let path = Path::new(name);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    LOCK_EXTENSIONS.contains(&ext) && LOCK_PREFIXES.iter().any(|p| stem.starts_with(p))
+}
+
+// True if `pid` is alive and looks like a sidecar we spawned, not a reused PID
+fn is_orphaned_sidecar(system: &System, pid: Pid) -> bool {
+    system.process(pid).is_some_and(|process| {
+        let name = process.name().to_string_lossy().to_lowercase();
+        name.starts_with("ovelo_server")
+            || process
+                .cmd()
+                .iter()
+                .any(|arg| arg.to_string_lossy().ends_with("sidecar.py"))
+    })
+}
+
+fn parse_pid(line: Option<&str>) -> Option<Pid> {
+    line?.trim().parse::<u32>().ok().map(Pid::from_u32)
+}
+
+// Returns false, touching nothing, when the app that wrote the PID file is still running:
+// that's another instance, and its sidecar and runtime files are live
+fn reconcile_pid_file(path: &Path, cleaned: &mut Vec<String>) -> bool {
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return true;
+    };
+    let mut lines = raw.lines();
+    let (sidecar_pid, owner_pid) = (parse_pid(lines.next()), parse_pid(lines.next()));
+
+    let mut system = System::new();
+    let pids: Vec<Pid> = sidecar_pid.into_iter().chain(owner_pid).collect();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&pids),
+        true,
+        ProcessRefreshKind::nothing().with_cmd(sysinfo::UpdateKind::Always),
+    );
+    if let Some(owner) = owner_pid {
+        if owner != Pid::from_u32(std::process::id()) && system.process(owner).is_some() {
+            println!(
+                "Startup cleanup: sidecar belongs to running instance (PID {})",
+                owner
+            );
+            return false;
+        }
+    }
+
+    // A sidecar outliving its app would answer readiness probes with stale state. Files
+    // from before the owner was recorded can't prove that, so their sidecar is left alone.
+    if let Some(pid) = sidecar_pid.filter(|_| owner_pid.is_some()) {
+        if is_orphaned_sidecar(&system, pid) {
+            if let Some(process) = system.process(pid) {
+                process.kill();
+                cleaned.push(format!("killed orphaned sidecar (PID {})", pid));
+            }
+        }
+    }
+    if std::fs::remove_file(path).is_ok() {
+        cleaned.push(format!("removed stale {}", path.display()));
+    }
+    true
+}
+
+/// Clears runtime files left behind by a crash: the PID file (killing the sidecar it names if
+/// its app is gone but it's still running), our own `.lock`/`.sock` files and interrupted
+/// temp writes. Skipped entirely while another instance owns them. Must run before the
+/// sidecar is started. Returns what was cleaned.
+pub fn reconcile_runtime_files(app_handle: &tauri::AppHandle) -> Vec<String> {
+    let mut cleaned = Vec::new();
+    if let Ok(pid_file) = paths::pid_file(app_handle) {
+        if !reconcile_pid_file(&pid_file, &mut cleaned) {
+            return cleaned;
+        }
+    }
+
+    if let Ok(entries) = paths::app_data_dir(app_handle)
+        .and_then(|dir| std::fs::read_dir(dir).map_err(|e| e.to_string()))
+    {
+        for entry in entries.flatten() {
+            let is_file = entry.file_type().is_ok_and(|t| t.is_file());
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if is_file && is_ours(&name) && std::fs::remove_file(entry.path()).is_ok() {
+                cleaned.push(format!("removed stale {}", name));
+            }
+        }
+    }
+
+    for item in &cleaned {
+        println!("Startup cleanup: {}", item);
+    }
+    cleaned
+}
@@ -0,0 +1,336 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::cache;
+use crate::python_bridge;
+
+// Settings live in the backend's user profile
+pub const SETTINGS_GET_ENDPOINT: &str = "/api/get_profile";
+const SETTINGS_UPDATE_ENDPOINT: &str = "/api/update_settings";
+
+const SETTINGS_TTL: Duration = Duration::from_secs(60);
+
+// Queued updates (slider drags) reach the backend at most this often
+const FLUSH_INTERVAL: Duration = Duration::from_millis(300);
+
+const CLOCK_FORMATS: &[&str] = &["12h", "24h"];
+const PRIVACY_LEVELS: &[&str] = &["smart", "minimal"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub focus_reminders: bool,
+    pub daily_summary: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            focus_reminders: true,
+            daily_summary: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrivacySettings {
+    pub data_collection: bool,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self {
+            data_collection: true,
+        }
+    }
+}
+
+/// User settings as stored in the profile. Unknown keys are preserved in `extra`
+/// so a round-trip through this struct never drops fields the shell doesn't model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub name: String,
+    pub email: String,
+    pub language: String,
+    pub timezone: String,
+    pub clock_format: String,
+    pub notifications: NotificationSettings,
+    pub privacy: PrivacySettings,
+    pub reflection_persona: String,
+    /// "smart" keeps window titles, "minimal" reduces them to app names
+    pub privacy_level: String,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            email: String::new(),
+            language: "en".to_string(),
+            timezone: "auto".to_string(),
+            clock_format: "12h".to_string(),
+            notifications: NotificationSettings::default(),
+            privacy: PrivacySettings::default(),
+            reflection_persona: "calm_coach".to_string(),
+            privacy_level: "smart".to_string(),
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !CLOCK_FORMATS.contains(&self.clock_format.as_str()) {
+            return Err(format!("Invalid clock format: {}", self.clock_format));
+        }
+        if !PRIVACY_LEVELS.contains(&self.privacy_level.as_str()) {
+            return Err(format!("Invalid privacy level: {}", self.privacy_level));
+        }
+        Ok(())
+    }
+}
+
+/// Recursively merges `patch` into `base`: objects merge key by key, anything else replaces.
+pub fn deep_merge(base: &mut serde_json::Value, patch: serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                deep_merge(base.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, patch) => *base = patch,
+    }
+}
+
+// `partial` merged over `current`, checked to still be valid settings
+fn merge_patch(current: &Settings, partial: serde_json::Value) -> Result<Settings, String> {
+    let mut merged = serde_json::to_value(current).map_err(|e| e.to_string())?;
+    deep_merge(&mut merged, partial);
+
+    let settings: Settings =
+        serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))?;
+    settings.validate()?;
+    Ok(settings)
+}
+
+/// Settings for reads, served from the cache while younger than SETTINGS_TTL.
+pub async fn fetch_settings() -> Result<Settings, String> {
+    let res = cache::call_api_cached(SETTINGS_GET_ENDPOINT, SETTINGS_TTL).await?;
+    serde_json::from_value(res).map_err(|e| format!("Malformed settings: {}", e))
+}
+
+// Writes merge over this rather than the cache, so a stale copy is never written back
+async fn fetch_settings_fresh() -> Result<Settings, String> {
+    cache::invalidate(SETTINGS_GET_ENDPOINT);
+    fetch_settings().await
+}
+
+/// Merges `partial` over the current settings, validates, and writes the result back.
+pub async fn apply_patch(partial: serde_json::Value) -> Result<Settings, String> {
+    if !partial.is_object() {
+        return Err("Settings patch must be an object".to_string());
+    }
+    let current = fetch_settings_fresh().await?;
+    let settings = merge_patch(&current, partial)?;
+
+    let body = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
+    let result = python_bridge::post_api(SETTINGS_UPDATE_ENDPOINT, body).await;
+    cache::invalidate(SETTINGS_GET_ENDPOINT);
+    result?;
+    Ok(settings)
+}
+
+/// Whether window titles must be hidden ("minimal" privacy). If settings can't be read
+/// we err on the side of privacy.
+pub async fn titles_redacted() -> bool {
+    fetch_settings()
+        .await
+        .map(|settings| settings.privacy_level == "minimal")
+        .unwrap_or(true)
+}
+
+#[derive(Default)]
+struct WriteQueue {
+    // Merged updates not yet sent
+    pending: Option<serde_json::Value>,
+    // Sent but not yet acknowledged, so reads don't flicker back to the old value
+    in_flight: Option<serde_json::Value>,
+    last_flush: Option<Instant>,
+    flushing: bool,
+}
+
+static WRITE_QUEUE: Mutex<Option<WriteQueue>> = Mutex::new(None);
+
+fn with_queue<T>(f: impl FnOnce(&mut WriteQueue) -> T) -> T {
+    f(WRITE_QUEUE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(WriteQueue::default))
+}
+
+// Sends queued updates one merged patch at a time, spaced FLUSH_INTERVAL apart,
+// until nothing is left (the trailing flush carries the final value)
+async fn flush_queue(app_handle: tauri::AppHandle) {
+    loop {
+        let wait = with_queue(|q| {
+            q.last_flush
+                .map(|t| FLUSH_INTERVAL.saturating_sub(t.elapsed()))
+                .unwrap_or_default()
+        });
+        tokio::time::sleep(wait).await;
+
+        let Some(patch) = with_queue(|q| {
+            let patch = q.pending.take();
+            if patch.is_none() {
+                q.flushing = false;
+            }
+            q.in_flight.clone_from(&patch);
+            patch
+        }) else {
+            return;
+        };
+
+        let result = apply_patch(patch).await;
+        with_queue(|q| {
+            q.in_flight = None;
+            q.last_flush = Some(Instant::now());
+        });
+        if let Err(e) = result {
+            eprintln!("Queued settings update failed: {}", e);
+            let _ = app_handle.emit("settings-write-failed", e);
+        }
+    }
+}
+
+/// Settings as stored, with any queued-but-unsent updates applied on top.
+#[tauri::command]
+pub async fn get_settings() -> Result<Settings, String> {
+    let settings = fetch_settings().await?;
+    let (in_flight, pending) = with_queue(|q| (q.in_flight.clone(), q.pending.clone()));
+    if in_flight.is_none() && pending.is_none() {
+        return Ok(settings);
+    }
+    let mut merged = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
+    for patch in [in_flight, pending].into_iter().flatten() {
+        deep_merge(&mut merged, patch);
+    }
+    serde_json::from_value(merged).map_err(|e| format!("Invalid settings: {}", e))
+}
+
+/// Coalesces rapid updates (e.g. while dragging a slider) into throttled writes.
+#[tauri::command]
+pub fn queue_settings_update(
+    app_handle: tauri::AppHandle,
+    partial: serde_json::Value,
+) -> Result<(), String> {
+    if !partial.is_object() {
+        return Err("Settings patch must be an object".to_string());
+    }
+    let start_flusher = with_queue(|q| {
+        deep_merge(
+            q.pending.get_or_insert_with(|| serde_json::json!({})),
+            partial,
+        );
+        !std::mem::replace(&mut q.flushing, true)
+    });
+    if start_flusher {
+        tauri::async_runtime::spawn(flush_queue(app_handle));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn patch_settings(partial: serde_json::Value) -> Result<Settings, String> {
+    apply_patch(partial).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deep_merge_merges_nested_objects() {
+        let mut base = json!({
+            "name": "Sam",
+            "notifications": { "focusReminders": true, "dailySummary": true },
+        });
+        deep_merge(
+            &mut base,
+            json!({ "notifications": { "dailySummary": false }, "language": "nl" }),
+        );
+        assert_eq!(
+            base,
+            json!({
+                "name": "Sam",
+                "language": "nl",
+                "notifications": { "focusReminders": true, "dailySummary": false },
+            })
+        );
+    }
+
+    #[test]
+    fn deep_merge_replaces_arrays_and_scalars() {
+        let mut base = json!({ "excludedApps": ["slack", "zoom"], "idle": 5 });
+        deep_merge(
+            &mut base,
+            json!({ "excludedApps": ["mail"], "idle": { "minutes": 3 } }),
+        );
+        assert_eq!(
+            base,
+            json!({ "excludedApps": ["mail"], "idle": { "minutes": 3 } })
+        );
+    }
+
+    #[test]
+    fn deep_merge_writes_nulls() {
+        let mut base = json!({ "privacy": { "dataCollection": true }, "name": "Sam" });
+        deep_merge(&mut base, json!({ "privacy": null, "avatar": null }));
+        assert_eq!(
+            base,
+            json!({ "privacy": null, "name": "Sam", "avatar": null })
+        );
+    }
+
+    #[test]
+    fn merge_patch_keeps_unpatched_and_unknown_fields() {
+        let mut current = Settings {
+            name: "Sam".to_string(),
+            ..Settings::default()
+        };
+        current
+            .extra
+            .insert("betaFlags".to_string(), json!({ "x": 1 }));
+
+        let merged = merge_patch(
+            &current,
+            json!({ "notifications": { "dailySummary": false } }),
+        )
+        .unwrap();
+        assert_eq!(merged.name, "Sam");
+        assert!(merged.notifications.focus_reminders);
+        assert!(!merged.notifications.daily_summary);
+        assert_eq!(merged.extra.get("betaFlags"), Some(&json!({ "x": 1 })));
+    }
+
+    #[test]
+    fn merge_patch_rejects_invalid_results() {
+        let current = Settings::default();
+        // A null over a modeled field isn't a valid setting, not a reset to the default
+        assert!(merge_patch(&current, json!({ "clockFormat": null })).is_err());
+        assert!(merge_patch(&current, json!({ "clockFormat": "13h" })).is_err());
+        // Unknown keys may be nulled; they're only passed through
+        let merged = merge_patch(&current, json!({ "betaFlags": null })).unwrap();
+        assert_eq!(
+            merged.extra.get("betaFlags"),
+            Some(&serde_json::Value::Null)
+        );
+    }
+}
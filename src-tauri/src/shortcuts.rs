@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::local_store::LocalStore;
+
+const SHORTCUTS_KEY: &str = "shortcuts";
+// toggle_window is handled here; the others are forwarded to the frontend as `shortcut-triggered`
+const ACTIONS: &[&str] = &["toggle_window", "pause_tracking", "quick_reflect"];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBinding {
+    action: &'static str,
+    accelerator: Option<String>,
+}
+
+fn load(app_handle: &tauri::AppHandle) -> BTreeMap<String, String> {
+    app_handle
+        .state::<LocalStore>()
+        .get(SHORTCUTS_KEY)
+        .unwrap_or_default()
+}
+
+fn bindings(mappings: &BTreeMap<String, String>) -> Vec<ShortcutBinding> {
+    ACTIONS
+        .iter()
+        .map(|action| ShortcutBinding {
+            action,
+            accelerator: mappings.get(*action).cloned(),
+        })
+        .collect()
+}
+
+fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    Shortcut::from_str(accelerator)
+        .map_err(|e| format!("Invalid shortcut {:?}: {}", accelerator, e))
+}
+
+fn trigger(app_handle: &tauri::AppHandle, action: &str) {
+    if action == "toggle_window" {
+        if let Some(window) = app_handle.get_webview_window("main") {
+            if window.is_visible().unwrap_or(false) {
+                let _ = window.hide();
+            } else {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }
+        return;
+    }
+    let _ = app_handle.emit("shortcut-triggered", action);
+}
+
+fn register(app_handle: &tauri::AppHandle, action: &str, shortcut: Shortcut) -> Result<(), String> {
+    let action = action.to_string();
+    app_handle
+        .global_shortcut()
+        .on_shortcut(shortcut, move |app_handle, _, event| {
+            if event.state == ShortcutState::Pressed {
+                trigger(app_handle, &action);
+            }
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Re-registers the persisted shortcuts; called from the setup hook.
+pub fn restore_shortcuts(app_handle: &tauri::AppHandle) {
+    for (action, accelerator) in load(app_handle) {
+        if let Err(e) = parse(&accelerator).and_then(|s| register(app_handle, &action, s)) {
+            eprintln!(
+                "Failed to restore shortcut {} for {}: {}",
+                accelerator, action, e
+            );
+        }
+    }
+}
+
+#[tauri::command]
+pub fn list_shortcuts(app_handle: tauri::AppHandle) -> Vec<ShortcutBinding> {
+    bindings(&load(&app_handle))
+}
+
+/// Binds `action` to `accelerator` (e.g. "CmdOrCtrl+Shift+P"), or unbinds it when empty.
+/// Fails without changing anything if another action already uses the combo or the OS
+/// refuses it, which usually means another application holds it.
+#[tauri::command]
+pub fn set_shortcut(
+    app_handle: tauri::AppHandle,
+    action: String,
+    accelerator: Option<String>,
+) -> Result<Vec<ShortcutBinding>, String> {
+    if !ACTIONS.contains(&action.as_str()) {
+        return Err(format!("Unknown shortcut action: {}", action));
+    }
+    let accelerator = accelerator
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
+    let shortcut = accelerator.as_deref().map(parse).transpose()?;
+
+    let mut mappings = load(&app_handle);
+    if let Some(shortcut) = shortcut {
+        // Compare parsed shortcuts so "Ctrl+Shift+P" and "shift+ctrl+p" count as the same combo
+        if let Some((other, _)) = mappings
+            .iter()
+            .find(|(other, existing)| **other != action && parse(existing).ok() == Some(shortcut))
+        {
+            return Err(format!(
+                "{} is already used by {}",
+                accelerator.unwrap_or_default(),
+                other
+            ));
+        }
+    }
+
+    let global = app_handle.global_shortcut();
+    let previous = mappings.get(&action).and_then(|a| parse(a).ok());
+    if previous.is_some() && previous == shortcut {
+        return Ok(bindings(&mappings));
+    }
+    if let Some(previous) = previous {
+        global.unregister(previous).map_err(|e| e.to_string())?;
+    }
+    if let Some(shortcut) = shortcut {
+        if let Err(e) = register(&app_handle, &action, shortcut) {
+            if let Some(previous) = previous {
+                let _ = register(&app_handle, &action, previous);
+            }
+            return Err(format!(
+                "Could not register {}, it may be in use by another application: {}",
+                accelerator.unwrap_or_default(),
+                e
+            ));
+        }
+    }
+
+    match accelerator {
+        Some(accelerator) => mappings.insert(action, accelerator),
+        None => mappings.remove(&action),
+    };
+    app_handle
+        .state::<LocalStore>()
+        .set(SHORTCUTS_KEY, &mappings)?;
+    Ok(bindings(&mappings))
+}
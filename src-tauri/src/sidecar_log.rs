@@ -0,0 +1,187 @@
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tauri::Emitter;
+use tokio::sync::mpsc;
+
+use crate::python_bridge;
+
+const LOG_FILE: &str = "sidecar.log";
+// Rotate once the active file passes this size, keeping a few older generations
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+const KEEP_ROTATED: usize = 3;
+// Live-tail lines are batched into one event per interval
+const TAIL_EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+// Verbosity levels understood by the backend's /api/log_level, least to most detailed
+const LOG_LEVELS: [&str; 4] = ["error", "warn", "info", "debug"];
+
+// The shell logs with plain println and has no level filter; the backend being at "debug"
+// only turns on the shell's debug-only output
+static DEBUG_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Whether the shell should print its debug-only output (prewarm timings, HTTP client
+/// resets, warm-cache results) and allow `debug_call`, in release builds.
+pub fn debug_enabled() -> bool {
+    DEBUG_OUTPUT.load(Ordering::Relaxed)
+}
+
+enum TailEvent {
+    Line(String),
+    Rotated(PathBuf),
+}
+
+struct ActiveLog {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+/// Persistent copy of the sidecar's stderr, with optional live tail to the webview.
+pub struct SidecarLog {
+    active: Mutex<Option<ActiveLog>>,
+    tail: Mutex<Option<mpsc::UnboundedSender<TailEvent>>>,
+}
+
+fn rotated_path(path: &Path, generation: usize) -> PathBuf {
+    path.with_extension(format!("{}.log", generation))
+}
+
+impl SidecarLog {
+    pub fn new() -> Self {
+        Self {
+            active: Mutex::new(None),
+            tail: Mutex::new(None),
+        }
+    }
+
+    /// Opens (or reopens) the log file inside `dir`, appending to what's there.
+    pub fn open(&self, dir: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        let path = dir.join(LOG_FILE);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| e.to_string())?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        *self.active.lock().unwrap() = Some(ActiveLog { path, file, size });
+        Ok(())
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        self.active
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|log| log.path.clone())
+    }
+
+    pub fn write_line(&self, line: &str) {
+        let mut rotated = None;
+        {
+            let mut active = self.active.lock().unwrap();
+            if let Some(log) = active.as_mut() {
+                if writeln!(log.file, "{}", line).is_ok() {
+                    log.size += line.len() as u64 + 1;
+                }
+                if log.size >= MAX_LOG_BYTES {
+                    match Self::rotate(log) {
+                        Ok(()) => rotated = Some(log.path.clone()),
+                        Err(e) => eprintln!("Failed to rotate sidecar log: {}", e),
+                    }
+                }
+            }
+        }
+
+        if let Some(tail) = self.tail.lock().unwrap().as_ref() {
+            let _ = tail.send(TailEvent::Line(line.to_string()));
+            if let Some(path) = rotated {
+                let _ = tail.send(TailEvent::Rotated(path));
+            }
+        }
+    }
+
+    // sidecar.log -> sidecar.1.log -> sidecar.2.log ..., dropping the oldest
+    fn rotate(log: &mut ActiveLog) -> std::io::Result<()> {
+        let _ = std::fs::remove_file(rotated_path(&log.path, KEEP_ROTATED));
+        for generation in (1..KEEP_ROTATED).rev() {
+            let from = rotated_path(&log.path, generation);
+            if from.exists() {
+                std::fs::rename(&from, rotated_path(&log.path, generation + 1))?;
+            }
+        }
+        std::fs::rename(&log.path, rotated_path(&log.path, 1))?;
+        log.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log.path)?;
+        log.size = 0;
+        Ok(())
+    }
+
+    /// Starts forwarding new lines and rotations to the webview. Restarting replaces the old tail.
+    pub fn start_tail(&self, app_handle: tauri::AppHandle) {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        *self.tail.lock().unwrap() = Some(tx);
+
+        tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(TAIL_EMIT_INTERVAL);
+            let mut lines: Vec<String> = Vec::new();
+            loop {
+                tokio::select! {
+                    event = rx.recv() => match event {
+                        Some(TailEvent::Line(line)) => lines.push(line),
+                        Some(TailEvent::Rotated(path)) => {
+                            let _ = app_handle.emit("sidecar-log-rotated", path);
+                        }
+                        None => break,
+                    },
+                    _ = interval.tick() => {
+                        if !lines.is_empty() {
+                            let _ = app_handle.emit("sidecar-log-line", std::mem::take(&mut lines));
+                        }
+                    }
+                }
+            }
+            if !lines.is_empty() {
+                let _ = app_handle.emit("sidecar-log-line", lines);
+            }
+        });
+    }
+
+    pub fn stop_tail(&self) {
+        // Dropping the sender ends the forwarding task
+        self.tail.lock().unwrap().take();
+    }
+}
+
+/// Changes backend log verbosity at runtime. At "debug" the shell's debug-only output is turned
+/// on as well (see `debug_enabled`). Returns the applied level.
+#[tauri::command]
+pub async fn set_backend_log_level(level: String) -> Result<String, String> {
+    let level = level.trim().to_lowercase();
+    if !LOG_LEVELS.contains(&level.as_str()) {
+        return Err(format!(
+            "Invalid log level: {} (expected one of {})",
+            level,
+            LOG_LEVELS.join(", ")
+        ));
+    }
+
+    let res =
+        python_bridge::post_api("/api/log_level", serde_json::json!({ "level": level })).await?;
+    let applied = res
+        .get("level")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&level)
+        .to_string();
+
+    DEBUG_OUTPUT.store(applied == "debug", Ordering::Relaxed);
+    println!("Log level set to {}", applied);
+    Ok(applied)
+}
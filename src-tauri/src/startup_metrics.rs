@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use crate::local_store::LocalStore;
+use crate::python_bridge::PythonSidecar;
+
+const METRICS_KEY: &str = "startup_metrics";
+// Upper bounds (ms) of the histogram buckets; one extra bucket holds anything slower
+const BUCKET_BOUNDS_MS: &[u64] = &[500, 1_000, 2_000, 4_000, 8_000, 15_000, 30_000];
+// A first launch slower than this gets a `startup-slow` event
+const SLOW_STARTUP_MS: u64 = 10_000;
+
+// Only the first start after launch is cold; restarts find the OS caches warm
+static COLD_START_RECORDED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Histogram {
+    buckets: Vec<u32>,
+    last_ms: Option<u64>,
+    max_ms: u64,
+}
+
+impl Histogram {
+    fn count(&self) -> u32 {
+        self.buckets.iter().sum()
+    }
+
+    fn record(&mut self, ms: u64) {
+        self.buckets.resize(BUCKET_BOUNDS_MS.len() + 1, 0);
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        self.buckets[bucket] += 1;
+        self.last_ms = Some(ms);
+        self.max_ms = self.max_ms.max(ms);
+    }
+
+    // Upper bound of the bucket holding the `p`th percentile; the overflow bucket reports the max seen
+    fn percentile(&self, p: f64) -> Option<u64> {
+        let rank = ((self.count() as f64) * p).ceil().max(1.0) as u32;
+        let mut seen = 0;
+        for (index, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return Some(BUCKET_BOUNDS_MS.get(index).copied().unwrap_or(self.max_ms));
+            }
+        }
+        None
+    }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StartupSlow {
+    elapsed_ms: u64,
+    threshold_ms: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupMetrics {
+    last_ms: Option<u64>,
+    p50_ms: Option<u64>,
+    p95_ms: Option<u64>,
+    count: u32,
+}
+
+/// Records spawn-to-ready time for the sidecar; called once it answers.
+pub fn record(app_handle: &tauri::AppHandle, sidecar: &PythonSidecar) {
+    // Nothing to time against a remote backend
+    let Some((_, started_at)) = sidecar.running_since() else {
+        return;
+    };
+    let elapsed_ms = started_at.elapsed().as_millis() as u64;
+    println!("Sidecar startup took {} ms", elapsed_ms);
+
+    let store = app_handle.state::<LocalStore>();
+    let mut histogram = store.get::<Histogram>(METRICS_KEY).unwrap_or_default();
+    histogram.record(elapsed_ms);
+    if let Err(e) = store.set(METRICS_KEY, &histogram) {
+        eprintln!("Failed to save startup metrics: {}", e);
+    }
+
+    if !COLD_START_RECORDED.swap(true, Ordering::Relaxed) && elapsed_ms > SLOW_STARTUP_MS {
+        let _ = app_handle.emit(
+            "startup-slow",
+            StartupSlow {
+                elapsed_ms,
+                threshold_ms: SLOW_STARTUP_MS,
+            },
+        );
+    }
+}
+
+/// Percentiles are bucket upper bounds, so they're coarse but cheap to keep across runs.
+#[tauri::command]
+pub fn get_startup_metrics(app_handle: tauri::AppHandle) -> StartupMetrics {
+    let histogram = app_handle
+        .state::<LocalStore>()
+        .get::<Histogram>(METRICS_KEY)
+        .unwrap_or_default();
+    StartupMetrics {
+        last_ms: histogram.last_ms,
+        p50_ms: histogram.percentile(0.5),
+        p95_ms: histogram.percentile(0.95),
+        count: histogram.count(),
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::local_store::LocalStore;
+use crate::paths;
+use crate::today_stream;
+
+const CONFIG_KEY: &str = "status_file";
+const DEFAULT_FILE: &str = "status.json";
+// At most one write per this interval; the last state seen is written when it elapses
+const MIN_WRITE_INTERVAL: Duration = Duration::from_secs(5);
+
+// Latest today_state the writer received, written straight away when the file is enabled
+static LAST_STATE: Mutex<Option<serde_json::Value>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusFileConfig {
+    pub enabled: bool,
+    /// Defaults to status.json in the backend data dir (e.g. ~/.ovelo/status.json)
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+}
+
+fn config(app_handle: &tauri::AppHandle) -> Option<StatusFileConfig> {
+    app_handle
+        .state::<LocalStore>()
+        .get::<StatusFileConfig>(CONFIG_KEY)
+        .filter(|config| config.enabled)
+}
+
+fn target(app_handle: &tauri::AppHandle, config: &StatusFileConfig) -> Result<PathBuf, String> {
+    match &config.path {
+        Some(path) => Ok(path.clone()),
+        None => Ok(paths::backend_data_dir(app_handle)?.join(DEFAULT_FILE)),
+    }
+}
+
+// Write-temp-then-rename so readers never see a half-written file
+fn write_atomic(path: &Path, state: &serde_json::Value) -> Result<(), String> {
+    let raw = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, raw).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+// Returns whether the file was written; a state seen while disabled is written by set_status_file
+fn write_status(app_handle: &tauri::AppHandle, state: &serde_json::Value) -> bool {
+    let Some(config) = config(app_handle) else {
+        return false;
+    };
+    let result = target(app_handle, &config).and_then(|path| write_atomic(&path, state));
+    if let Err(e) = &result {
+        eprintln!("Failed to write status file: {}", e);
+    }
+    result.is_ok()
+}
+
+/// Mirrors today_state updates into the status file while it's enabled.
+pub fn start_writer(app_handle: tauri::AppHandle) {
+    let mut updates = today_stream::subscribe();
+    tauri::async_runtime::spawn(async move {
+        let mut last_written: Option<serde_json::Value> = None;
+        let mut last_write_at: Option<Instant> = None;
+        let mut pending: Option<serde_json::Value> = None;
+        loop {
+            let wait = match (&pending, last_write_at) {
+                (Some(_), Some(at)) => MIN_WRITE_INTERVAL.saturating_sub(at.elapsed()),
+                (Some(_), None) => Duration::ZERO,
+                // Nothing to write; just wait for the next update
+                (None, _) => Duration::MAX,
+            };
+            tokio::select! {
+                update = updates.recv() => match update {
+                    Ok(state) => {
+                        *LAST_STATE.lock().unwrap() = Some(state.clone());
+                        if last_written.as_ref() != Some(&state) {
+                            pending = Some(state);
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => return,
+                },
+                _ = tokio::time::sleep(wait) => {
+                    if let Some(state) = pending.take() {
+                        if write_status(&app_handle, &state) {
+                            last_write_at = Some(Instant::now());
+                            last_written = Some(state);
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Turns the status file on or off. Enabling checks that the file can be written and writes
+/// the current state, so it exists before today_state next changes.
+#[tauri::command]
+pub fn set_status_file(
+    app_handle: tauri::AppHandle,
+    config: StatusFileConfig,
+) -> Result<StatusFileConfig, String> {
+    if config.enabled {
+        if let Some(path) = &config.path {
+            if !path.is_absolute() {
+                return Err("Status file path must be absolute".to_string());
+            }
+        }
+        let path = target(&app_handle, &config)?;
+        let dir = path
+            .parent()
+            .ok_or_else(|| format!("Invalid status file path: {}", path.display()))?;
+        if !dir.is_dir() {
+            return Err(format!("Folder does not exist: {}", dir.display()));
+        }
+        paths::check_writable(dir)?;
+        if let Some(state) = LAST_STATE.lock().unwrap().clone() {
+            write_atomic(&path, &state)?;
+        }
+    }
+    app_handle.state::<LocalStore>().set(CONFIG_KEY, &config)?;
+    Ok(config)
+}
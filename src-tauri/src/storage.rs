@@ -0,0 +1,453 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager, State};
+
+use crate::cache;
+use crate::integrity;
+use crate::local_store::LocalStore;
+use crate::paths;
+use crate::python_bridge::{self, PythonSidecar};
+
+// VACUUM rewrites the whole database and can take a while on a large history
+const VACUUM_TIMEOUT: Duration = Duration::from_secs(600);
+// How long a data dir move waits for in-flight saves before stopping the sidecar
+const MOVE_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsage {
+    total_bytes: u64,
+    db_bytes: u64,
+    reflections_bytes: u64,
+    backups_bytes: u64,
+}
+
+#[derive(Clone, Copy)]
+enum Category {
+    Db,
+    Reflections,
+    Backups,
+    Other,
+}
+
+fn categorize(path: &Path, in_backups: bool) -> Category {
+    if in_backups {
+        return Category::Backups;
+    }
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    if name.contains("reflection") {
+        Category::Reflections
+    } else if name == "ovelo_data.json" || name.ends_with(".db") || name.ends_with(".sqlite") {
+        Category::Db
+    } else {
+        Category::Other
+    }
+}
+
+// Symlinks are counted as themselves and never followed, so a link loop can't recurse forever
+fn walk(dir: &Path, in_backups: bool, usage: &mut StorageUsage) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            let backups = in_backups || path.file_name().is_some_and(|n| n == "backups");
+            walk(&path, backups, usage);
+            continue;
+        }
+        let size = meta.len();
+        usage.total_bytes += size;
+        match categorize(&path, in_backups) {
+            Category::Db => usage.db_bytes += size,
+            Category::Reflections => usage.reflections_bytes += size,
+            Category::Backups => usage.backups_bytes += size,
+            Category::Other => {}
+        }
+    }
+}
+
+async fn measure(dir: PathBuf) -> Result<StorageUsage, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut usage = StorageUsage::default();
+        walk(&dir, false, &mut usage);
+        usage
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_storage_usage(app_handle: tauri::AppHandle) -> Result<StorageUsage, String> {
+    measure(paths::backend_data_dir(&app_handle)?).await
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactResult {
+    before_bytes: u64,
+    after_bytes: u64,
+    reclaimed_bytes: u64,
+}
+
+/// Asks the backend to VACUUM its database. Refuses while other writes are in flight,
+/// since VACUUM locks the database for its whole duration.
+#[tauri::command]
+pub async fn compact_database(app_handle: tauri::AppHandle) -> Result<CompactResult, String> {
+    let pending = python_bridge::mutations_in_flight();
+    if pending > 0 {
+        return Err(format!(
+            "Cannot compact while {} write(s) are in progress, try again shortly",
+            pending
+        ));
+    }
+
+    let dir = paths::backend_data_dir(&app_handle)?;
+    let before_bytes = measure(dir.clone()).await?.db_bytes;
+
+    let _ = app_handle.emit("compacting", true);
+    let result =
+        python_bridge::post_api_with_timeout("/api/vacuum", serde_json::json!({}), VACUUM_TIMEOUT)
+            .await;
+    let _ = app_handle.emit("compacting", false);
+    result?;
+
+    let after_bytes = measure(dir).await?.db_bytes;
+    Ok(CompactResult {
+        before_bytes,
+        after_bytes,
+        reclaimed_bytes: before_bytes.saturating_sub(after_bytes),
+    })
+}
+
+// Progress events are throttled to one per this many copied bytes
+const MOVE_PROGRESS_STEP: u64 = 4 * 1024 * 1024;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MoveProgress {
+    copied_bytes: u64,
+    total_bytes: u64,
+}
+
+// Regular files under `dir` as (relative path, size). Symlinks are skipped, like `walk`.
+fn list_files(dir: &Path, rel: &Path, files: &mut Vec<(PathBuf, u64)>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir.join(rel))? {
+        let entry = entry?;
+        let meta = std::fs::symlink_metadata(entry.path())?;
+        let rel_path = rel.join(entry.file_name());
+        if meta.is_dir() {
+            list_files(dir, &rel_path, files)?;
+        } else if meta.is_file() {
+            files.push((rel_path, meta.len()));
+        }
+    }
+    Ok(())
+}
+
+// Copies every file, then checks each copy's size and SHA-256 against the original
+fn copy_tree(app_handle: &tauri::AppHandle, from: &Path, to: &Path) -> Result<(), String> {
+    let mut files = Vec::new();
+    list_files(from, Path::new(""), &mut files)
+        .map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    let total_bytes: u64 = files.iter().map(|(_, size)| size).sum();
+
+    let mut copied_bytes = 0;
+    let mut next_report = 0;
+    for (rel, size) in &files {
+        let dest = to.join(rel);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::copy(from.join(rel), &dest)
+            .map_err(|e| format!("Failed to copy {}: {}", rel.display(), e))?;
+        copied_bytes += size;
+        if copied_bytes >= next_report {
+            let _ = app_handle.emit(
+                "data-dir-move-progress",
+                MoveProgress {
+                    copied_bytes,
+                    total_bytes,
+                },
+            );
+            next_report = copied_bytes + MOVE_PROGRESS_STEP;
+        }
+    }
+
+    for (rel, size) in &files {
+        let dest = to.join(rel);
+        let copied = std::fs::metadata(&dest).map(|m| m.len()).ok();
+        if copied != Some(*size) {
+            return Err(format!("Verification failed for {}", rel.display()));
+        }
+        let hash = |path: &Path| {
+            integrity::sha256_file(path)
+                .map_err(|e| format!("Failed to verify {}: {}", rel.display(), e))
+        };
+        if hash(&from.join(rel))? != hash(&dest)? {
+            return Err(format!("Verification failed for {}", rel.display()));
+        }
+    }
+    let _ = app_handle.emit(
+        "data-dir-move-progress",
+        MoveProgress {
+            copied_bytes,
+            total_bytes,
+        },
+    );
+    Ok(())
+}
+
+// Brings the sidecar back on `stored` (None = default location) after a failed move
+async fn roll_back(
+    app_handle: &tauri::AppHandle,
+    sidecar: &Arc<PythonSidecar>,
+    stored: Option<PathBuf>,
+    new_dir: &Path,
+    created: bool,
+) {
+    sidecar.stop();
+    let store = app_handle.state::<LocalStore>();
+    let restored = match &stored {
+        Some(dir) => store.set(paths::DATA_DIR_KEY, dir),
+        None => store.remove(paths::DATA_DIR_KEY),
+    };
+    if let Err(e) = restored {
+        eprintln!("Failed to restore data dir pointer: {}", e);
+    }
+    // Only the copy is removed; the original was never touched
+    let cleanup = if created {
+        std::fs::remove_dir_all(new_dir)
+    } else {
+        std::fs::read_dir(new_dir).and_then(|entries| {
+            entries.flatten().try_for_each(|entry| {
+                let path = entry.path();
+                if path.is_dir() {
+                    std::fs::remove_dir_all(path)
+                } else {
+                    std::fs::remove_file(path)
+                }
+            })
+        })
+    };
+    if let Err(e) = cleanup {
+        eprintln!("Failed to clean up {}: {}", new_dir.display(), e);
+    }
+    sidecar.start_async(app_handle).await;
+    crate::spawn_ready_watch(app_handle.clone(), sidecar.clone());
+}
+
+/// Moves all backend data to `new_path`. The old copy is only deleted once the sidecar is
+/// confirmed running against the new one; any earlier failure rolls back to the old location.
+#[tauri::command]
+pub async fn move_data_dir(
+    app_handle: tauri::AppHandle,
+    sidecar: State<'_, Arc<PythonSidecar>>,
+    new_path: String,
+) -> Result<String, String> {
+    python_bridge::ensure_writable()?;
+    if paths::data_dir_override().is_some() {
+        return Err("Cannot move data while a test data dir is active".to_string());
+    }
+    let old_dir = paths::backend_data_dir(&app_handle)?;
+    let new_dir = PathBuf::from(new_path.trim());
+    if !new_dir.is_absolute() {
+        return Err("Destination must be an absolute path".to_string());
+    }
+    if new_dir.starts_with(&old_dir) || old_dir.starts_with(&new_dir) {
+        return Err("Destination can't be inside the current data dir or contain it".to_string());
+    }
+    let created = !new_dir.exists();
+    if !created
+        && std::fs::read_dir(&new_dir)
+            .map_err(|e| e.to_string())?
+            .next()
+            .is_some()
+    {
+        return Err(format!("Destination is not empty: {}", new_dir.display()));
+    }
+    std::fs::create_dir_all(&new_dir).map_err(|e| e.to_string())?;
+    paths::check_writable(&new_dir)?;
+
+    let sidecar = sidecar.inner().clone();
+    let stored = app_handle
+        .state::<LocalStore>()
+        .get::<PathBuf>(paths::DATA_DIR_KEY);
+
+    // Let pending saves land before the files are copied
+    python_bridge::drain_mutations(MOVE_DRAIN_TIMEOUT).await?;
+    sidecar.stop();
+
+    let copied = {
+        let app_handle = app_handle.clone();
+        let (from, to) = (old_dir.clone(), new_dir.clone());
+        tokio::task::spawn_blocking(move || copy_tree(&app_handle, &from, &to))
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|result| result)
+    };
+    let switched = copied.and_then(|()| {
+        app_handle
+            .state::<LocalStore>()
+            .set(paths::DATA_DIR_KEY, &new_dir)
+    });
+    if let Err(e) = switched {
+        roll_back(&app_handle, &sidecar, stored, &new_dir, created).await;
+        return Err(format!("Data dir move failed, nothing was changed: {}", e));
+    }
+
+    sidecar.start_async(&app_handle).await;
+    if let Err(e) = sidecar.wait_until_ready().await {
+        roll_back(&app_handle, &sidecar, stored, &new_dir, created).await;
+        return Err(format!(
+            "Sidecar failed to start on the new data dir, moved back: {}",
+            e
+        ));
+    }
+    let _ = app_handle.emit("sidecar-ready", ());
+
+    if let Err(e) = std::fs::remove_dir_all(&old_dir) {
+        eprintln!("Failed to remove old data dir {}: {}", old_dir.display(), e);
+    }
+    println!("Data dir moved to {}", new_dir.display());
+    Ok(new_dir.to_string_lossy().into_owned())
+}
+
+// Typed by the user in the wipe dialog; keeps a stray invoke from erasing everything
+const WIPE_CONFIRMATION: &str = "DELETE ALL MY DATA";
+const WIPE_CHUNK: usize = 64 * 1024;
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WipeReport {
+    files_removed: u64,
+    bytes_removed: u64,
+    /// Anything still on disk afterwards; empty means the wipe was verified
+    remaining: Vec<String>,
+}
+
+// Overwrites a file with zeros and flushes it before it's unlinked. On SSDs and
+// copy-on-write filesystems the old blocks may survive elsewhere, so this is best effort.
+fn overwrite(path: &Path, size: u64) -> std::io::Result<()> {
+    use std::io::Write;
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    let zeros = [0u8; WIPE_CHUNK];
+    let mut left = size;
+    while left > 0 {
+        let n = left.min(WIPE_CHUNK as u64) as usize;
+        file.write_all(&zeros[..n])?;
+        left -= n as u64;
+    }
+    file.sync_all()
+}
+
+// Overwrites and deletes everything inside `dir`, leaving the (empty) dir itself
+fn wipe_dir(dir: &Path, report: &mut WipeReport) -> std::io::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    let mut files = Vec::new();
+    list_files(dir, Path::new(""), &mut files)?;
+    for (rel, size) in &files {
+        let path = dir.join(rel);
+        if let Err(e) = overwrite(&path, *size) {
+            eprintln!("Could not overwrite {}: {}", path.display(), e);
+        }
+        std::fs::remove_file(&path)?;
+        report.files_removed += 1;
+        report.bytes_removed += size;
+    }
+    // Whatever is left is directories and symlinks; neither is followed
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if std::fs::symlink_metadata(&path)?.is_dir() {
+            std::fs::remove_dir_all(&path)?;
+        } else {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn remaining_entries(dir: &Path) -> Vec<String> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path().display().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Stops the backend, overwrites and deletes every file it and the shell keep, checks that
+/// nothing is left, and restarts into a first-run state. `confirmation` must be the exact
+/// phrase the wipe dialog asks the user to type.
+#[tauri::command]
+pub async fn secure_wipe(
+    app_handle: tauri::AppHandle,
+    sidecar: State<'_, Arc<PythonSidecar>>,
+    confirmation: String,
+) -> Result<WipeReport, String> {
+    python_bridge::ensure_writable()?;
+    if confirmation != WIPE_CONFIRMATION {
+        return Err(format!(
+            "Type \"{}\" to confirm wiping all data",
+            WIPE_CONFIRMATION
+        ));
+    }
+    if python_bridge::api_endpoint().is_remote() {
+        return Err("Data on a remote backend can't be wiped from here".to_string());
+    }
+    let backend_dir = paths::backend_data_dir(&app_handle)?;
+    let shell_dir = paths::app_data_dir(&app_handle)?;
+
+    let sidecar = sidecar.inner().clone();
+    python_bridge::drain_mutations(MOVE_DRAIN_TIMEOUT).await?;
+    sidecar.stop();
+    sidecar.log().stop_tail();
+    app_handle.state::<LocalStore>().clear()?;
+    cache::clear();
+
+    let report = {
+        let dirs = [backend_dir.clone(), shell_dir.clone()];
+        tokio::task::spawn_blocking(move || {
+            let mut report = WipeReport::default();
+            for dir in &dirs {
+                wipe_dir(dir, &mut report)
+                    .map_err(|e| format!("Wipe of {} failed: {}", dir.display(), e))?;
+            }
+            for dir in &dirs {
+                report.remaining.extend(remaining_entries(dir));
+            }
+            Ok::<_, String>(report)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    };
+    // Whatever happened, don't leave the user without a backend
+    sidecar.start_async(&app_handle).await;
+    crate::spawn_ready_watch(app_handle.clone(), sidecar);
+
+    let report = report?;
+    if report.remaining.is_empty() {
+        println!(
+            "Secure wipe removed {} files ({} bytes)",
+            report.files_removed, report.bytes_removed
+        );
+    } else {
+        eprintln!("Secure wipe left {} entries behind", report.remaining.len());
+    }
+    let _ = app_handle.emit("data-wiped", ());
+    Ok(report)
+}
@@ -0,0 +1,176 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use crate::python_bridge;
+
+const SYNC_STATUS_ENDPOINT: &str = "/api/sync_status";
+const SYNC_TIMEOUT: Duration = Duration::from_secs(120);
+// While a manual sync runs, its progress is read from the status endpoint this often
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+// How long disabling waits for the backend to report its outbound traffic has stopped
+const STOP_CONFIRM_TIMEOUT: Duration = Duration::from_secs(5);
+const STOP_CONFIRM_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncStatus {
+    pub enabled: bool,
+    #[serde(default, alias = "last_sync")]
+    pub last_sync: Option<String>,
+    #[serde(default, alias = "pending_changes")]
+    pub pending_changes: u32,
+    /// "syncing", "idle", "error" or "disabled"
+    #[serde(default = "default_state")]
+    pub state: String,
+    #[serde(default, alias = "outbound_active", skip_serializing)]
+    outbound_active: bool,
+}
+
+/// A record changed on both sides since the last sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conflict {
+    #[serde(alias = "record_type")]
+    record_type: String,
+    #[serde(alias = "record_id")]
+    record_id: String,
+    #[serde(default, alias = "local_updated_at")]
+    local_updated_at: Option<String>,
+    #[serde(default, alias = "remote_updated_at")]
+    remote_updated_at: Option<String>,
+    /// "local" or "remote" if the backend resolved it, None when the user has to choose
+    #[serde(default)]
+    kept: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncResult {
+    #[serde(default)]
+    uploaded: u32,
+    #[serde(default)]
+    downloaded: u32,
+    #[serde(default)]
+    conflicts: Vec<Conflict>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncProgress {
+    pending_changes: u32,
+    elapsed_ms: u64,
+}
+
+// Held for the whole of a manual sync so two can't overlap
+static SYNC_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+fn default_state() -> String {
+    "idle".to_string()
+}
+
+async fn fetch_status() -> Result<SyncStatus, String> {
+    let json = python_bridge::call_api(SYNC_STATUS_ENDPOINT).await?;
+    serde_json::from_value(json).map_err(|e| format!("Invalid sync status: {}", e))
+}
+
+fn emit_status(app_handle: &tauri::AppHandle, status: &SyncStatus) {
+    let _ = app_handle.emit("sync-status", status);
+}
+
+/// Polls the backend's sync state and emits `sync-status` whenever it changes.
+pub fn start_watcher(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last: Option<SyncStatus> = None;
+        loop {
+            if python_bridge::is_deferred() {
+                tokio::time::sleep(POLL_INTERVAL).await;
+                continue;
+            }
+            if let Ok(status) = fetch_status().await {
+                if last.as_ref() != Some(&status) {
+                    emit_status(&app_handle, &status);
+                    last = Some(status);
+                }
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn get_sync_status() -> Result<SyncStatus, String> {
+    fetch_status().await
+}
+
+/// Switches between local-only and sync. Disabling only succeeds once the backend reports
+/// no outbound sync traffic, so "off" in the UI means nothing is leaving the machine.
+#[tauri::command]
+pub async fn set_sync_enabled(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<SyncStatus, String> {
+    python_bridge::post_api(
+        "/api/sync_enabled",
+        serde_json::json!({ "enabled": enabled }),
+    )
+    .await?;
+
+    let started = Instant::now();
+    let status = loop {
+        let status = fetch_status().await?;
+        if enabled || (!status.enabled && !status.outbound_active) {
+            break status;
+        }
+        if started.elapsed() >= STOP_CONFIRM_TIMEOUT {
+            emit_status(&app_handle, &status);
+            return Err("Backend did not confirm that sync traffic stopped".to_string());
+        }
+        tokio::time::sleep(STOP_CONFIRM_INTERVAL).await;
+    };
+    emit_status(&app_handle, &status);
+    Ok(status)
+}
+
+/// Runs a sync right away, emitting `sync-progress` while it's in flight.
+#[tauri::command]
+pub async fn sync_now(app_handle: tauri::AppHandle) -> Result<SyncResult, String> {
+    let _guard = SYNC_LOCK
+        .try_lock()
+        .map_err(|_| "A sync is already running".to_string())?;
+
+    let started = Instant::now();
+    let sync =
+        python_bridge::post_api_with_timeout("/api/sync", serde_json::json!({}), SYNC_TIMEOUT);
+    tokio::pin!(sync);
+    let mut progress = tokio::time::interval(PROGRESS_INTERVAL);
+    progress.tick().await;
+    let result = loop {
+        tokio::select! {
+            result = &mut sync => break result,
+            _ = progress.tick() => {
+                if let Ok(status) = fetch_status().await {
+                    let _ = app_handle.emit(
+                        "sync-progress",
+                        SyncProgress {
+                            pending_changes: status.pending_changes,
+                            elapsed_ms: started.elapsed().as_millis() as u64,
+                        },
+                    );
+                }
+            }
+        }
+    };
+
+    if let Ok(status) = fetch_status().await {
+        emit_status(&app_handle, &status);
+    }
+    let result: SyncResult =
+        serde_json::from_value(result?).map_err(|e| format!("Invalid sync result: {}", e))?;
+    if !result.conflicts.is_empty() {
+        println!("Sync finished with {} conflicts", result.conflicts.len());
+    }
+    Ok(result)
+}
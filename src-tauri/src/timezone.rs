@@ -0,0 +1,65 @@
+use serde::Serialize;
+use tauri::Emitter;
+
+use crate::python_bridge;
+
+const TIMEZONE_ENDPOINT: &str = "/api/timezone";
+// The backend following the OS clock can't disagree with it
+const AUTO: &str = "auto";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimezoneStatus {
+    backend: String,
+    os: Option<String>,
+    matches: bool,
+}
+
+fn os_timezone() -> Option<String> {
+    iana_time_zone::get_timezone().ok()
+}
+
+async fn backend_timezone() -> Result<String, String> {
+    let res = python_bridge::call_api(TIMEZONE_ENDPOINT).await?;
+    Ok(res
+        .get("timezone")
+        .and_then(|tz| tz.as_str())
+        .unwrap_or(AUTO)
+        .to_string())
+}
+
+/// Compares the backend's notion of local time with the OS, emitting `timezone-mismatch`
+/// when they disagree so date-keyed views can warn before showing the wrong day.
+pub async fn reconcile(app_handle: &tauri::AppHandle) -> Result<TimezoneStatus, String> {
+    let backend = backend_timezone().await?;
+    let os = os_timezone();
+    let matches = backend == AUTO || os.as_deref().is_none_or(|os| os == backend);
+    let status = TimezoneStatus {
+        backend,
+        os,
+        matches,
+    };
+    if !status.matches {
+        let _ = app_handle.emit("timezone-mismatch", status.clone());
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub async fn get_backend_timezone(app_handle: tauri::AppHandle) -> Result<TimezoneStatus, String> {
+    reconcile(&app_handle).await
+}
+
+/// Sets the backend timezone to an IANA name (e.g. "Europe/Brussels"), or "auto" to follow the OS.
+#[tauri::command]
+pub async fn set_backend_timezone(
+    app_handle: tauri::AppHandle,
+    tz: String,
+) -> Result<TimezoneStatus, String> {
+    let tz = tz.trim();
+    if tz != AUTO && tz.parse::<chrono_tz::Tz>().is_err() {
+        return Err(format!("Unknown timezone: {}", tz));
+    }
+    python_bridge::post_api(TIMEZONE_ENDPOINT, serde_json::json!({ "timezone": tz })).await?;
+    reconcile(&app_handle).await
+}
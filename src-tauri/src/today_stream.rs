@@ -0,0 +1,145 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use tauri::Emitter;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::python_bridge;
+
+/// Minimum gap between `today-state` emits to the webview; tune here.
+pub const EMIT_INTERVAL: Duration = Duration::from_millis(250);
+
+// Polling cadence when the backend has no SSE endpoint (matches Config.TRACKING_INTERVAL)
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+const CHANNEL_CAPACITY: usize = 64;
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+// Every raw update, for Rust-side watchers (alerts, goals, ...) that shouldn't be throttled
+static UPDATES: OnceLock<broadcast::Sender<serde_json::Value>> = OnceLock::new();
+
+fn updates() -> &'static broadcast::Sender<serde_json::Value> {
+    UPDATES.get_or_init(|| broadcast::channel(CHANNEL_CAPACITY).0)
+}
+
+pub fn subscribe() -> broadcast::Receiver<serde_json::Value> {
+    updates().subscribe()
+}
+
+/// Starts streaming today_state to the webview. Safe to call repeatedly; only the first call
+/// spawns the stream, which reconnects on its own for the rest of the app's lifetime.
+pub fn start(app_handle: tauri::AppHandle) {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    tauri::async_runtime::spawn(coalesce(app_handle, rx));
+    tauri::async_runtime::spawn(run_source(tx));
+}
+
+// Throttles updates to one emit per EMIT_INTERVAL, always flushing the latest value once idle
+async fn coalesce(app_handle: tauri::AppHandle, mut rx: mpsc::Receiver<serde_json::Value>) {
+    let mut pending: Option<serde_json::Value> = None;
+    let mut last_emit: Option<Instant> = None;
+
+    loop {
+        let next = match &pending {
+            Some(_) => {
+                let since = last_emit.map(|t| t.elapsed()).unwrap_or(EMIT_INTERVAL);
+                let wait = EMIT_INTERVAL.saturating_sub(since);
+                tokio::time::timeout(wait, rx.recv()).await
+            }
+            None => Ok(rx.recv().await),
+        };
+
+        match next {
+            Ok(Some(state)) => {
+                let _ = updates().send(state.clone());
+                pending = Some(state);
+            }
+            Ok(None) => {
+                if let Some(state) = pending.take() {
+                    let _ = app_handle.emit("today-state", state);
+                }
+                return;
+            }
+            Err(_) => {} // throttle window elapsed with nothing new; flush below
+        }
+
+        let due = last_emit.is_none_or(|t| t.elapsed() >= EMIT_INTERVAL);
+        if due {
+            if let Some(state) = pending.take() {
+                let _ = app_handle.emit("today-state", state);
+                last_emit = Some(Instant::now());
+            }
+        }
+    }
+}
+
+async fn run_source(tx: mpsc::Sender<serde_json::Value>) {
+    let mut use_sse = true;
+    loop {
+        if use_sse {
+            match python_bridge::get_raw("/api/today_stream").await {
+                Ok(res) => {
+                    if let Err(e) = read_sse(res, &tx).await {
+                        eprintln!("today_state stream dropped: {}", e);
+                    }
+                    if tx.is_closed() {
+                        return;
+                    }
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                    continue;
+                }
+                Err(e) => println!("today_state SSE unavailable ({}), polling instead", e),
+            }
+        }
+
+        // Older backends have no SSE endpoint; poll, and once the backend answers stick with polling
+        if let Ok(state) = python_bridge::call_api("/today_state").await {
+            use_sse = false;
+            if tx.send(state).await.is_err() {
+                return;
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+// Minimal text/event-stream reader: events are blank-line separated, payload in `data:` lines
+async fn read_sse(
+    res: reqwest::Response,
+    tx: &mpsc::Sender<serde_json::Value>,
+) -> Result<(), String> {
+    let mut stream = res.bytes_stream();
+    let mut buffer = String::new();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| e.to_string())?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk).replace("\r\n", "\n"));
+
+        while let Some(end) = buffer.find("\n\n") {
+            let event: String = buffer.drain(..end + 2).collect();
+            let data: Vec<&str> = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim_start)
+                .collect();
+            if data.is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&data.join("\n")) {
+                Ok(state) => {
+                    if tx.send(state).await.is_err() {
+                        return Ok(());
+                    }
+                }
+                Err(e) => eprintln!("Skipping malformed today_state event: {}", e),
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::{Emitter, Manager};
+
+use crate::activity;
+use crate::auto_pause;
+use crate::local_store::LocalStore;
+use crate::notifications;
+use crate::paths;
+
+pub const TRAY_ID: &str = "main";
+
+const CLICK_KEY: &str = "tray_click_behavior";
+const CLICK_MODES: &[&str] = &["toggle", "menu", "show"];
+const DEFAULT_CLICK_MODE: &str = "show";
+
+const QUICK_ACTIONS_KEY: &str = "tray_quick_actions";
+// Id and menu label of every action the tray can offer between Show and Quit
+const QUICK_ACTIONS: &[(&str, &str)] = &[
+    ("pause", "Pause tracking"),
+    ("resume", "Resume tracking"),
+    ("reflect-today", "Reflect on today"),
+    ("open-data", "Open data folder"),
+    ("snooze", "Snooze notifications for 1 hour"),
+];
+const SNOOZE_DURATION: Duration = Duration::from_secs(3600);
+
+fn click_mode(app_handle: &tauri::AppHandle) -> String {
+    app_handle
+        .state::<LocalStore>()
+        .get::<String>(CLICK_KEY)
+        .unwrap_or_else(|| DEFAULT_CLICK_MODE.to_string())
+}
+
+fn apply_click_mode(app_handle: &tauri::AppHandle, mode: &str) -> Result<(), String> {
+    let tray = app_handle
+        .tray_by_id(TRAY_ID)
+        .ok_or_else(|| "Tray icon not found".to_string())?;
+    tray.set_show_menu_on_left_click(mode == "menu")
+        .map_err(|e| e.to_string())
+}
+
+/// Handles a left click on the tray icon according to the configured mode.
+pub fn on_left_click(app_handle: &tauri::AppHandle) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+    match click_mode(app_handle).as_str() {
+        // The OS opens the menu itself
+        "menu" => {}
+        "toggle" if window.is_visible().unwrap_or(false) => {
+            let _ = window.hide();
+        }
+        _ => {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+/// Re-applies the saved click behavior once the tray exists; called from the setup hook.
+pub fn restore_click_behavior(app_handle: &tauri::AppHandle) {
+    if let Err(e) = apply_click_mode(app_handle, &click_mode(app_handle)) {
+        eprintln!("Failed to restore tray click behavior: {}", e);
+    }
+}
+
+/// Sets what left-clicking the tray does: "toggle" the window, open the "menu", or "show" it.
+#[tauri::command]
+pub fn set_tray_click_behavior(
+    app_handle: tauri::AppHandle,
+    mode: String,
+) -> Result<String, String> {
+    if !CLICK_MODES.contains(&mode.as_str()) {
+        return Err(format!("Unknown tray click behavior: {}", mode));
+    }
+
+    apply_click_mode(&app_handle, &mode)?;
+    app_handle.state::<LocalStore>().set(CLICK_KEY, &mode)?;
+    Ok(mode)
+}
+
+fn quick_actions(app_handle: &tauri::AppHandle) -> Vec<String> {
+    app_handle
+        .state::<LocalStore>()
+        .get::<Vec<String>>(QUICK_ACTIONS_KEY)
+        .unwrap_or_default()
+}
+
+/// Show, the selected quick actions, then Quit.
+pub fn build_menu(app_handle: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app_handle)?;
+    menu.append(&MenuItem::with_id(
+        app_handle,
+        "show",
+        "Show Ovelo",
+        true,
+        None::<&str>,
+    )?)?;
+    let selected = quick_actions(app_handle);
+    // Menu order follows QUICK_ACTIONS, not the order they were picked in
+    for (id, label) in QUICK_ACTIONS
+        .iter()
+        .filter(|(id, _)| selected.iter().any(|s| s == id))
+    {
+        menu.append(&MenuItem::with_id(
+            app_handle,
+            *id,
+            *label,
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    if !selected.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+    }
+    menu.append(&MenuItem::with_id(
+        app_handle,
+        "quit",
+        "Quit",
+        true,
+        None::<&str>,
+    )?)?;
+    Ok(menu)
+}
+
+fn show_main_window(app_handle: &tauri::AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn open_data_dir(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let dir = paths::backend_data_dir(app_handle)?;
+    app_handle
+        .opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+fn run_quick_action(app_handle: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    match id {
+        "pause" | "resume" => {
+            let (app_handle, paused) = (app_handle.clone(), id == "pause");
+            tauri::async_runtime::spawn(async move {
+                match activity::set_tracking_paused(paused).await {
+                    Ok(()) if !paused => auto_pause::on_manual_resume(&app_handle),
+                    Ok(()) => {}
+                    Err(e) => eprintln!("Tray pause/resume failed: {}", e),
+                }
+            });
+            Ok(())
+        }
+        // The reflection flow lives in the frontend; open it there
+        "reflect-today" => {
+            show_main_window(app_handle);
+            let _ = app_handle.emit("tray-action", id);
+            Ok(())
+        }
+        "open-data" => open_data_dir(app_handle),
+        "snooze" => {
+            let until = chrono::Utc::now()
+                + chrono::Duration::from_std(SNOOZE_DURATION).map_err(|e| e.to_string())?;
+            notifications::snooze(app_handle, until)?;
+            let _ = app_handle.emit("tray-action", id);
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn on_menu_event(app_handle: &tauri::AppHandle, id: &str) {
+    match id {
+        "show" => show_main_window(app_handle),
+        "quit" => app_handle.exit(0),
+        _ => {
+            if let Err(e) = run_quick_action(app_handle, id) {
+                eprintln!("Tray action {} failed: {}", id, e);
+            }
+        }
+    }
+}
+
+/// Picks which quick actions the tray menu offers; Show and Quit are always there.
+#[tauri::command]
+pub fn set_tray_quick_actions(
+    app_handle: tauri::AppHandle,
+    actions: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let mut selected: Vec<String> = Vec::new();
+    for action in actions {
+        if !QUICK_ACTIONS.iter().any(|(id, _)| *id == action) {
+            return Err(format!("Unknown tray action: {}", action));
+        }
+        if !selected.contains(&action) {
+            selected.push(action);
+        }
+    }
+
+    let store = app_handle.state::<LocalStore>();
+    let previous = quick_actions(&app_handle);
+    store.set(QUICK_ACTIONS_KEY, &selected)?;
+    let tray = app_handle
+        .tray_by_id(TRAY_ID)
+        .ok_or_else(|| "Tray icon not found".to_string())?;
+    let rebuilt = build_menu(&app_handle)
+        .and_then(|menu| tray.set_menu(Some(menu)))
+        .map_err(|e| e.to_string());
+    if let Err(e) = rebuilt {
+        store.set(QUICK_ACTIONS_KEY, &previous)?;
+        return Err(format!("Failed to rebuild tray menu: {}", e));
+    }
+    Ok(selected)
+}
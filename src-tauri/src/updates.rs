@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri_plugin_updater::UpdaterExt;
+
+// A dead update server shouldn't keep the check spinning
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateStatus {
+    UpToDate,
+    UpdateAvailable,
+    CheckFailed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheck {
+    pub status: UpdateStatus,
+    /// The new version when one is available, the reason when the check failed
+    pub detail: Option<String>,
+}
+
+/// Checks the update server. Never errors: being offline or a server outage comes back as
+/// `check-failed` so the UI can show a quiet note instead of blocking anything.
+#[tauri::command]
+pub async fn check_for_update(app_handle: tauri::AppHandle) -> UpdateCheck {
+    let checked = match app_handle.updater_builder().timeout(CHECK_TIMEOUT).build() {
+        Ok(updater) => updater.check().await,
+        Err(e) => Err(e),
+    };
+
+    match checked {
+        Ok(Some(update)) => UpdateCheck {
+            status: UpdateStatus::UpdateAvailable,
+            detail: Some(update.version),
+        },
+        Ok(None) => UpdateCheck {
+            status: UpdateStatus::UpToDate,
+            detail: None,
+        },
+        Err(e) => {
+            eprintln!("Update check failed: {}", e);
+            UpdateCheck {
+                status: UpdateStatus::CheckFailed,
+                detail: Some(e.to_string()),
+            }
+        }
+    }
+}
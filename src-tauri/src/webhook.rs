@@ -0,0 +1,168 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tokio::sync::mpsc;
+
+use crate::local_store::LocalStore;
+
+const CONFIG_KEY: &str = "webhook";
+const EVENTS: &[&str] = &["focus_started", "focus_milestone", "focus_ended"];
+const TIMEOUT: Duration = Duration::from_secs(5);
+// Attempts per delivery, with RETRY_DELAY doubling in between
+const ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+// Deliveries waiting behind a slow endpoint; beyond this new ones are dropped
+const QUEUE_CAPACITY: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub events: Vec<String>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload {
+    event: String,
+    minutes: u32,
+    timestamp: String,
+    source: &'static str,
+}
+
+impl WebhookPayload {
+    fn new(event: &str, minutes: u32) -> Self {
+        Self {
+            event: event.to_string(),
+            minutes,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            source: "ovelo",
+        }
+    }
+}
+
+struct Delivery {
+    url: String,
+    payload: WebhookPayload,
+}
+
+// Separate from the sidecar client: webhooks leave the machine, so system proxies apply
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(TIMEOUT)
+            .build()
+            .expect("failed to build webhook client")
+    })
+}
+
+fn validate_url(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("Webhook URL must use http or https".to_string());
+    }
+    if parsed.host_str().is_none() {
+        return Err("Webhook URL has no host".to_string());
+    }
+    Ok(())
+}
+
+async fn send(url: &str, payload: &WebhookPayload) -> Result<reqwest::StatusCode, String> {
+    client()
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map(|res| res.status())
+        .map_err(|e| e.to_string())
+}
+
+async fn deliver(delivery: Delivery) {
+    let mut delay = RETRY_DELAY;
+    for attempt in 1..=ATTEMPTS {
+        match send(&delivery.url, &delivery.payload).await {
+            // Client errors won't get better by retrying
+            Ok(status) if status.is_success() || status.is_client_error() => {
+                if !status.is_success() {
+                    eprintln!("Webhook rejected {}: {}", delivery.payload.event, status);
+                }
+                return;
+            }
+            Ok(status) => eprintln!("Webhook attempt {} failed: {}", attempt, status),
+            Err(e) => eprintln!("Webhook attempt {} failed: {}", attempt, e),
+        }
+        if attempt < ATTEMPTS {
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+    }
+}
+
+// One worker delivers in order, so a down endpoint costs a single task and a bounded queue
+fn queue() -> &'static mpsc::Sender<Delivery> {
+    static QUEUE: OnceLock<mpsc::Sender<Delivery>> = OnceLock::new();
+    QUEUE.get_or_init(|| {
+        let (tx, mut rx) = mpsc::channel(QUEUE_CAPACITY);
+        tauri::async_runtime::spawn(async move {
+            while let Some(delivery) = rx.recv().await {
+                deliver(delivery).await;
+            }
+        });
+        tx
+    })
+}
+
+fn config(app_handle: &tauri::AppHandle) -> Option<WebhookConfig> {
+    app_handle.state::<LocalStore>().get(CONFIG_KEY)
+}
+
+/// Queues `event` for the configured webhook if it subscribes to it. Never waits.
+pub fn dispatch(app_handle: &tauri::AppHandle, event: &str, minutes: u32) {
+    let Some(config) = config(app_handle) else {
+        return;
+    };
+    if !config.events.iter().any(|e| e == event) {
+        return;
+    }
+    let delivery = Delivery {
+        url: config.url,
+        payload: WebhookPayload::new(event, minutes),
+    };
+    if queue().try_send(delivery).is_err() {
+        eprintln!("Webhook queue full, dropped {}", event);
+    }
+}
+
+/// Sets the webhook that focus events are POSTed to; an empty URL turns it off.
+#[tauri::command]
+pub fn set_webhook(
+    app_handle: tauri::AppHandle,
+    config: WebhookConfig,
+) -> Result<Option<WebhookConfig>, String> {
+    let store = app_handle.state::<LocalStore>();
+    let url = config.url.trim().to_string();
+    if url.is_empty() {
+        store.remove(CONFIG_KEY)?;
+        return Ok(None);
+    }
+    validate_url(&url)?;
+    if let Some(unknown) = config.events.iter().find(|e| !EVENTS.contains(&e.as_str())) {
+        return Err(format!("Unknown webhook event: {}", unknown));
+    }
+    let config = WebhookConfig {
+        url,
+        events: config.events,
+    };
+    store.set(CONFIG_KEY, &config)?;
+    Ok(Some(config))
+}
+
+/// Sends a sample payload to the configured webhook and returns the HTTP status.
+#[tauri::command]
+pub async fn test_webhook(app_handle: tauri::AppHandle) -> Result<u16, String> {
+    let config = config(&app_handle).ok_or_else(|| "No webhook configured".to_string())?;
+    let status = send(&config.url, &WebhookPayload::new("test", 0)).await?;
+    Ok(status.as_u16())
+}
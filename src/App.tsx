@@ -102,7 +102,14 @@ function App() {
     // Auto-update check
     const checkForUpdates = async () => {
       try {
-        const update = await check();
+        let update;
+        try {
+          update = await check({ timeout: 10000 });
+        } catch (error) {
+          // Offline or update server down: note it quietly and carry on
+          console.warn("Couldn't check for updates:", error);
+          return;
+        }
         if (update?.available) {
           sendNotification({
             title: 'Update Available',
@@ -126,11 +133,7 @@ function App() {
           console.log("No update available.");
         }
       } catch (error) {
-        console.error('Error checking for updates:', error);
-        sendNotification({
-          title: 'Update Check Failed',
-          body: `Error: ${error}`
-        });
+        console.error('Error installing update:', error);
       }
     };
     checkForUpdates();
@@ -150,6 +153,11 @@ function App() {
 
   async function checkProfile() {
     try {
+      const firstRun: boolean = await invoke("is_first_run");
+      if (firstRun) {
+        navigate('/onboarding');
+        return;
+      }
       const profile: any = await invoke("get_profile");
       if (profile && Object.keys(profile).length > 0) {
         if (profile.reflectionPersona) {
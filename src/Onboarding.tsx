@@ -65,6 +65,7 @@ function Onboarding() {
         const profile = buildFocusProfile();
         try {
             await invoke("save_profile", { profile });
+            await invoke("complete_onboarding");
             navigate('/');
         } catch (error) {
             console.error('Error saving profile:', error);